@@ -0,0 +1,249 @@
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::reader::Reader;
+use std::path::Path;
+
+use crate::references::describe_location;
+use crate::utils::attributes::get_attribute;
+use crate::utils::{initialize_out_dir, write_text_file};
+
+const REFERENCE_TYPES: [&str; 5] = [
+    "FieldReference",
+    "ScriptReference",
+    "LayoutReference",
+    "ValueListReference",
+    "TableOccurrenceReference",
+];
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct BrokenReference {
+    pub object_type: String,
+    pub object: String,
+    pub path: Vec<String>,
+    pub step: Option<usize>,
+    pub reference_type: String,
+    pub reference: String,
+}
+
+pub fn write_broken_references(
+    out_dir_path: &Path,
+    fm_file_name: &str,
+    references: &[BrokenReference],
+) {
+    let out_dir_path = out_dir_path.join("reports").join(fm_file_name);
+    initialize_out_dir(&out_dir_path);
+
+    write_text_file(
+        &out_dir_path.join("broken_references.txt"),
+        &to_text(references),
+    );
+    write_text_file(
+        &out_dir_path.join("broken_references.json"),
+        &to_json(references),
+    );
+}
+
+fn to_text(references: &[BrokenReference]) -> String {
+    references
+        .iter()
+        .map(|reference| {
+            format!(
+                "{} ; {}: {}",
+                describe_location(
+                    &reference.object_type,
+                    &reference.object,
+                    &reference.path,
+                    reference.step
+                ),
+                reference.reference_type,
+                reference.reference
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn to_json(references: &[BrokenReference]) -> String {
+    let entries = references
+        .iter()
+        .map(|reference| {
+            format!(
+                "  {{\n    \"object_type\": {},\n    \"object\": {},\n    \"path\": [{}],\n    \"step\": {},\n    \"reference_type\": {},\n    \"reference\": {}\n  }}",
+                json_string(&reference.object_type),
+                json_string(&reference.object),
+                reference
+                    .path
+                    .iter()
+                    .map(|path| json_string(path))
+                    .collect::<Vec<_>>()
+                    .join(", "),
+                reference
+                    .step
+                    .map_or("null".to_string(), |step| step.to_string()),
+                json_string(&reference.reference_type),
+                json_string(&reference.reference),
+            )
+        })
+        .collect::<Vec<_>>();
+
+    match entries.is_empty() {
+        true => "[]".to_string(),
+        false => format!("[\n{}\n]", entries.join(",\n")),
+    }
+}
+
+/// Returns type and description of a reference element that points to nothing.
+pub fn broken_reference(e: &BytesStart) -> Option<(String, String)> {
+    let reference_type = std::str::from_utf8(e.name().as_ref()).ok()?.to_string();
+    if !REFERENCE_TYPES.contains(&reference_type.as_str()) {
+        return None;
+    }
+
+    let id = get_attribute(e, "id").unwrap_or_default();
+    let name = get_attribute(e, "name").unwrap_or_default();
+    if id != "0" && !name.is_empty() {
+        return None;
+    }
+
+    Some((
+        reference_type,
+        format!(
+            "{} (ID {})",
+            match name.is_empty() {
+                true => "<missing>",
+                false => name.as_str(),
+            },
+            id
+        ),
+    ))
+}
+
+pub fn find_broken_references(xml: &str) -> Vec<(String, String)> {
+    let mut references: Vec<(String, String)> = Vec::new();
+    let mut broken_field: Option<usize> = None;
+
+    let mut reader = Reader::from_str(xml);
+    let mut buf = Vec::new();
+    loop {
+        match reader.read_event_into(&mut buf) {
+            Err(_) | Ok(Event::Eof) => break,
+            Ok(Event::Start(e)) | Ok(Event::Empty(e)) => {
+                if let (Some(index), b"TableOccurrenceReference") =
+                    (broken_field, e.name().as_ref())
+                {
+                    let table_occurrence = get_attribute(&e, "name").unwrap_or_default();
+                    let reference = &mut references[index].1;
+                    *reference = format!("{}::{}", table_occurrence, reference);
+                    broken_field = None;
+                    continue;
+                }
+
+                if let Some(reference) = broken_reference(&e) {
+                    if reference.0 == "FieldReference" {
+                        broken_field = Some(references.len());
+                    }
+                    references.push(reference);
+                }
+            }
+            Ok(Event::End(e)) if e.name().as_ref() == b"FieldReference" => broken_field = None,
+            _ => {}
+        }
+        buf.clear()
+    }
+
+    references
+}
+
+fn json_string(value: &str) -> String {
+    let mut escaped = String::from("\"");
+    for char in value.chars() {
+        match char {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            _ if char.is_control() => escaped.push_str(&format!("\\u{:04x}", char as u32)),
+            _ => escaped.push(char),
+        }
+    }
+    escaped.push('"');
+
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_broken_references() {
+        let xml_input = "
+        <Step id=\"76\" name=\"Feldwert setzen\" enable=\"True\">
+            <ParameterValues membercount=\"2\">
+                <Parameter type=\"FieldReference\">
+                    <FieldReference id=\"0\" name=\"\">
+                        <repetition value=\"1\"></repetition>
+                        <TableOccurrenceReference id=\"1065132\" name=\"_Color\"></TableOccurrenceReference>
+                    </FieldReference>
+                </Parameter>
+                <Parameter type=\"FieldReference\">
+                    <FieldReference id=\"1\" name=\"Name\">
+                        <TableOccurrenceReference id=\"1065132\" name=\"_Color\"></TableOccurrenceReference>
+                    </FieldReference>
+                </Parameter>
+                <Parameter type=\"ScriptReference\">
+                    <ScriptReference id=\"0\" name=\"\"></ScriptReference>
+                </Parameter>
+                <Parameter type=\"LayoutReference\">
+                    <LayoutReference id=\"12\" name=\"Start\"></LayoutReference>
+                </Parameter>
+            </ParameterValues>
+        </Step>
+        ";
+
+        assert_eq!(
+            find_broken_references(xml_input),
+            vec![
+                (
+                    "FieldReference".to_string(),
+                    "_Color::<missing> (ID 0)".to_string()
+                ),
+                (
+                    "ScriptReference".to_string(),
+                    "<missing> (ID 0)".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_report_output() {
+        let references = vec![
+            BrokenReference {
+                object_type: "Script".to_string(),
+                object: "All steps - ID 9".to_string(),
+                path: vec!["Examples - ID 46".to_string()],
+                step: Some(3),
+                reference_type: "FieldReference".to_string(),
+                reference: "<missing> (ID 0)".to_string(),
+            },
+            BrokenReference {
+                object_type: "Layout".to_string(),
+                object: "Start \"Home\" - ID 1".to_string(),
+                path: vec![],
+                step: None,
+                reference_type: "ValueListReference".to_string(),
+                reference: "<missing> (ID 0)".to_string(),
+            },
+        ];
+
+        assert_eq!(
+            to_text(&references),
+            "Script: Examples - ID 46/All steps - ID 9 ; Step: 3 ; FieldReference: <missing> (ID 0)\nLayout: Start \"Home\" - ID 1 ; ValueListReference: <missing> (ID 0)"
+        );
+        assert_eq!(
+            to_json(&references),
+            "[\n  {\n    \"object_type\": \"Script\",\n    \"object\": \"All steps - ID 9\",\n    \"path\": [\"Examples - ID 46\"],\n    \"step\": 3,\n    \"reference_type\": \"FieldReference\",\n    \"reference\": \"<missing> (ID 0)\"\n  },\n  {\n    \"object_type\": \"Layout\",\n    \"object\": \"Start \\\"Home\\\" - ID 1\",\n    \"path\": [],\n    \"step\": null,\n    \"reference_type\": \"ValueListReference\",\n    \"reference\": \"<missing> (ID 0)\"\n  }\n]"
+        );
+    }
+}
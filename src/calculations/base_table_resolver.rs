@@ -0,0 +1,127 @@
+use crate::table_occurrence_catalog::TableOccurrence;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+thread_local! {
+    static CURRENT_RESOLVER: RefCell<Option<BaseTableResolver>> = const { RefCell::new(None) };
+}
+
+/// Resolves table occurrences to the base tables they are based on. Table occurrences of
+/// external data sources are skipped, their base tables belong to another file.
+#[derive(Debug, Default, Clone)]
+pub struct BaseTableResolver {
+    base_tables: Arc<HashMap<String, (String, String)>>,
+}
+
+impl BaseTableResolver {
+    pub fn new(table_occurrences: &[TableOccurrence]) -> Self {
+        BaseTableResolver {
+            base_tables: Arc::new(
+                table_occurrences
+                    .iter()
+                    .filter(|table_occurrence| {
+                        !table_occurrence.is_external && !table_occurrence.base_table_id.is_empty()
+                    })
+                    .map(|table_occurrence| {
+                        (
+                            table_occurrence.id.to_string(),
+                            (
+                                table_occurrence.base_table_id.to_string(),
+                                table_occurrence.base_table_name.to_string(),
+                            ),
+                        )
+                    })
+                    .collect(),
+            ),
+        }
+    }
+
+    /// Returns id and name of the base table of the given table occurrence.
+    pub fn base_table(&self, table_occurrence_id: &str) -> Option<(&str, &str)> {
+        self.base_tables
+            .get(table_occurrence_id)
+            .map(|(id, name)| (id.as_str(), name.as_str()))
+    }
+
+    pub fn base_table_name(&self, table_occurrence_id: &str) -> Option<&str> {
+        self.base_table(table_occurrence_id).map(|(_, name)| name)
+    }
+
+    /// Makes [`FieldReference::display`](crate::calculations::field_reference::FieldReference::display)
+    /// render `TO::Field (BaseTable)` while running `f`.
+    pub fn apply<T>(&self, f: impl FnOnce() -> T) -> T {
+        let previous = CURRENT_RESOLVER.with(|resolver| resolver.replace(Some(self.clone())));
+        let result = f();
+        CURRENT_RESOLVER.with(|resolver| resolver.replace(previous));
+        result
+    }
+
+    /// The base table to annotate a reference to the given table occurrence with, if any.
+    /// Table occurrences with the same name as their base table aren't annotated.
+    pub fn annotation(table_occurrence_id: &str, table_occurrence_name: &str) -> Option<String> {
+        CURRENT_RESOLVER.with(|resolver| {
+            resolver
+                .borrow()
+                .as_ref()?
+                .base_table_name(table_occurrence_id)
+                .filter(|base_table| *base_table != table_occurrence_name)
+                .map(str::to_string)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn resolver() -> BaseTableResolver {
+        BaseTableResolver::new(&[
+            table_occurrence("1065089", "130", "Customers", false),
+            table_occurrence("1065090", "130", "Customers", false),
+            table_occurrence("1065091", "131", "Invoices", false),
+            table_occurrence("1065092", "130", "Archive", true),
+        ])
+    }
+
+    fn table_occurrence(
+        id: &str,
+        base_table_id: &str,
+        base_table_name: &str,
+        is_external: bool,
+    ) -> TableOccurrence {
+        TableOccurrence {
+            id: id.to_string(),
+            base_table_id: base_table_id.to_string(),
+            base_table_name: base_table_name.to_string(),
+            is_external,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_base_table_name() {
+        let resolver = resolver();
+        assert_eq!(resolver.base_table_name("1065090"), Some("Customers"));
+        assert_eq!(resolver.base_table_name("1065091"), Some("Invoices"));
+        assert_eq!(resolver.base_table_name("1065092"), None);
+        assert_eq!(resolver.base_table_name("1"), None);
+    }
+
+    #[test]
+    fn test_annotation() {
+        assert_eq!(
+            BaseTableResolver::annotation("1065090", "Invoices_Customers"),
+            None
+        );
+
+        resolver().apply(|| {
+            assert_eq!(
+                BaseTableResolver::annotation("1065090", "Invoices_Customers"),
+                Some("Customers".to_string())
+            );
+            assert_eq!(BaseTableResolver::annotation("1065091", "Invoices"), None);
+            assert_eq!(BaseTableResolver::annotation("1065092", "Archive_TO"), None);
+        });
+    }
+}
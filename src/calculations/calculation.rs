@@ -1,23 +1,165 @@
+use crate::calculations::field_reference::FieldReference;
+use crate::calculations::function_names::{canonical_function_name, FunctionLanguage};
+use crate::calculations::pretty_print::pretty_print;
 use crate::utils;
+use crate::utils::attributes::get_attribute;
+use crate::Settings;
 use quick_xml::events::{BytesStart, Event};
 use quick_xml::Reader;
+use std::cell::Cell;
 
-#[derive(Debug, Default)]
-pub struct Calculation {}
+/// How calculations are rendered in the sanitized output.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct CalculationFormat {
+    pub english_function_names: bool,
+    pub pretty_print: bool,
+}
+
+thread_local! {
+    static CURRENT_FORMAT: Cell<CalculationFormat> = Cell::new(CalculationFormat::default());
+}
+
+impl CalculationFormat {
+    pub fn new(settings: &Settings) -> Self {
+        CalculationFormat {
+            english_function_names: settings.function_language == FunctionLanguage::En,
+            pretty_print: settings.pretty_print_calculations,
+        }
+    }
+
+    /// Renders the calculations of [`Calculation::from_xml`] in this format while running `f`,
+    /// so the script step sanitizers don't have to pass it through.
+    pub fn apply<T>(self, f: impl FnOnce() -> T) -> T {
+        let previous = CURRENT_FORMAT.with(|format| format.replace(self));
+        let result = f();
+        CURRENT_FORMAT.with(|format| format.set(previous));
+        result
+    }
+
+    pub fn current() -> Self {
+        CURRENT_FORMAT.with(Cell::get)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Chunk {
+    NoRef(String),
+    FunctionRef(String),
+    CustomFunctionRef(String),
+    VariableReference(String),
+    FieldRef(FieldReference, String),
+    Other(String),
+}
+
+impl Chunk {
+    pub fn new(chunk_type: &str, text: String) -> Self {
+        match chunk_type {
+            "NoRef" => Chunk::NoRef(text),
+            "FunctionRef" => Chunk::FunctionRef(text),
+            "CustomFunctionRef" => Chunk::CustomFunctionRef(text),
+            "VariableReference" => Chunk::VariableReference(text),
+            "FieldRef" => Chunk::FieldRef(FieldReference::default(), text),
+            _ => Chunk::Other(text),
+        }
+    }
+
+    pub fn text(&self) -> &str {
+        match self {
+            Chunk::NoRef(text)
+            | Chunk::FunctionRef(text)
+            | Chunk::CustomFunctionRef(text)
+            | Chunk::VariableReference(text)
+            | Chunk::FieldRef(_, text)
+            | Chunk::Other(text) => text,
+        }
+    }
+
+    pub fn set_text(&mut self, value: String) {
+        match self {
+            Chunk::NoRef(text)
+            | Chunk::FunctionRef(text)
+            | Chunk::CustomFunctionRef(text)
+            | Chunk::VariableReference(text)
+            | Chunk::FieldRef(_, text)
+            | Chunk::Other(text) => *text = value,
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct Calculation {
+    pub text: String,
+    pub chunks: Vec<Chunk>,
+}
 
 impl Calculation {
-    pub fn from_xml(reader: &mut Reader<&[u8]>, _: &BytesStart) -> Result<String, String> {
+    /// Aligns the chunks with the calculation text, so that the chunk texts add up to the
+    /// original text including whitespace. Field reference chunks get their text from the gap
+    /// between their neighbours. If the chunks don't match the text, they are kept as they are.
+    pub fn new(text: String, chunks: Vec<Chunk>) -> Self {
+        if chunks.is_empty() {
+            return Calculation { text, chunks };
+        }
+
+        let mut aligned: Vec<Chunk> = Vec::new();
+        let mut pending_field: Option<Chunk> = None;
+        let mut cursor = 0;
+
+        for chunk in &chunks {
+            if let Chunk::FieldRef(..) = chunk {
+                pending_field = Some(chunk.clone());
+                continue;
+            }
+            if chunk.text().is_empty() {
+                continue;
+            }
+
+            let position = match text[cursor..].find(chunk.text()) {
+                Some(position) => cursor + position,
+                None => return Calculation { text, chunks },
+            };
+            push_gap(&mut aligned, pending_field.take(), &text[cursor..position]);
+            push_chunk(&mut aligned, chunk.clone());
+            cursor = position + chunk.text().len();
+        }
+        push_gap(&mut aligned, pending_field.take(), &text[cursor..]);
+
+        Calculation {
+            text,
+            chunks: aligned,
+        }
+    }
+
+    pub fn parse(reader: &mut Reader<&[u8]>, _: &BytesStart) -> Result<Self, String> {
         let mut depth = 1;
         let mut in_text = false;
-        let mut calculation = String::new();
+        let mut in_chunk = false;
+        let mut text = String::new();
+        let mut chunks: Vec<Chunk> = Vec::new();
         let mut buf: Vec<u8> = Vec::new();
         loop {
             match reader.read_event_into(&mut buf) {
                 Err(_) => continue,
                 Ok(Event::Start(e)) => {
                     depth += 1;
-                    if e.name().as_ref() == b"Text" {
-                        in_text = true
+                    match e.name().as_ref() {
+                        b"Text" => in_text = true,
+                        b"ChunkList" => chunks.clear(),
+                        b"Chunk" => {
+                            in_chunk = true;
+                            chunks.push(Chunk::new(
+                                get_attribute(&e, "type").unwrap_or_default().as_str(),
+                                String::new(),
+                            ));
+                        }
+                        b"FieldReference" if in_chunk => {
+                            let field_reference = FieldReference::from_xml(reader, &e).unwrap();
+                            depth -= 1;
+                            if let Some(Chunk::FieldRef(reference, _)) = chunks.last_mut() {
+                                *reference = field_reference;
+                            }
+                        }
+                        _ => {}
                     }
                 }
                 Ok(Event::Eof) => break,
@@ -25,7 +167,12 @@ impl Calculation {
                     if !in_text {
                         continue;
                     }
-                    calculation = utils::xml_utils::cdata_to_string(&e);
+                    text = utils::xml_utils::cdata_to_string(&e);
+                }
+                Ok(Event::Text(e)) if in_chunk => {
+                    if let Some(chunk) = chunks.last_mut() {
+                        chunk.set_text(utils::xml_utils::text_to_string(&e));
+                    }
                 }
                 Ok(Event::End(e)) => {
                     depth -= 1;
@@ -33,8 +180,10 @@ impl Calculation {
                         break;
                     }
 
-                    if e.name().as_ref() == b"Text" {
-                        in_text = false
+                    match e.name().as_ref() {
+                        b"Text" => in_text = false,
+                        b"Chunk" => in_chunk = false,
+                        _ => {}
                     }
                 }
                 _ => {}
@@ -42,6 +191,245 @@ impl Calculation {
             buf.clear();
         }
 
-        Ok(calculation)
+        Ok(Calculation::new(text, chunks))
+    }
+
+    pub fn from_xml(reader: &mut Reader<&[u8]>, e: &BytesStart) -> Result<String, String> {
+        Ok(Calculation::parse(reader, e)?.render(CalculationFormat::current()))
+    }
+
+    pub fn render(&self, format: CalculationFormat) -> String {
+        if format.pretty_print {
+            return pretty_print(self, format);
+        }
+        if self.chunks.is_empty() {
+            return self.text.clone();
+        }
+
+        self.formatted_chunks(format)
+            .iter()
+            .map(|chunk| chunk.text())
+            .collect()
+    }
+
+    /// The chunks with their function names replaced by the English names if requested.
+    /// Function names inside string literals and placeholders like
+    /// `<Funktion NICHT vorhanden>` are kept.
+    pub fn formatted_chunks(&self, format: CalculationFormat) -> Vec<Chunk> {
+        let mut preceding = String::new();
+        let mut chunks: Vec<Chunk> = Vec::new();
+        for chunk in &self.chunks {
+            chunks.push(match chunk {
+                Chunk::FunctionRef(name)
+                    if format.english_function_names && !is_in_literal(&preceding) =>
+                {
+                    Chunk::FunctionRef(canonical_function_name(name).to_string())
+                }
+                chunk => chunk.clone(),
+            });
+            preceding.push_str(chunk.text());
+        }
+        chunks
+    }
+}
+
+fn is_in_literal(preceding: &str) -> bool {
+    let mut in_string = false;
+    let mut escaped = false;
+    let mut placeholder: Option<usize> = None;
+    for (index, c) in preceding.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if in_string => escaped = true,
+            '"' => in_string = !in_string,
+            '<' if !in_string => placeholder = Some(index + 1),
+            '>' if !in_string => placeholder = None,
+            _ => {}
+        }
+    }
+
+    in_string
+        || placeholder.is_some_and(|start| {
+            let inner = &preceding[start..];
+            inner.starts_with(char::is_alphabetic)
+                && inner.chars().all(|c| c.is_alphanumeric() || c == ' ')
+        })
+}
+
+fn push_chunk(chunks: &mut Vec<Chunk>, chunk: Chunk) {
+    match (chunks.last_mut(), chunk) {
+        (Some(Chunk::NoRef(text)), Chunk::NoRef(next)) => text.push_str(&next),
+        (_, chunk) => chunks.push(chunk),
+    }
+}
+
+fn push_gap(chunks: &mut Vec<Chunk>, pending_field: Option<Chunk>, gap: &str) {
+    let Some(Chunk::FieldRef(reference, _)) = pending_field else {
+        if !gap.is_empty() {
+            push_chunk(chunks, Chunk::NoRef(gap.to_string()));
+        }
+        return;
+    };
+
+    let field_text = gap.trim();
+    let (leading, rest) = gap.split_at(gap.find(field_text).unwrap_or(0));
+    let trailing = &rest[field_text.len()..];
+    if !leading.is_empty() {
+        push_chunk(chunks, Chunk::NoRef(leading.to_string()));
+    }
+    chunks.push(Chunk::FieldRef(reference, field_text.to_string()));
+    if !trailing.is_empty() {
+        chunks.push(Chunk::NoRef(trailing.to_string()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ENGLISH: CalculationFormat = CalculationFormat {
+        english_function_names: true,
+        pretty_print: false,
+    };
+
+    fn parse(xml_input: &str) -> Calculation {
+        let mut reader = Reader::from_str(xml_input);
+        reader.trim_text(true);
+        let element = match reader.read_event() {
+            Ok(Event::Start(e)) => e,
+            _ => panic!("Wrong read event"),
+        };
+
+        Calculation::parse(&mut reader, &element).unwrap()
+    }
+
+    #[test]
+    fn test_localized_function_names() {
+        let calculation = parse(
+            "
+            <Calculation datatype=\"1\" position=\"0\">
+                <Calculation>
+                    <Text><![CDATA[Hole( LayoutNummer ) + 1]]></Text>
+                    <ChunkList hash=\"92D1CC21320F97E522578F76FF6F2651\">
+                        <Chunk type=\"FunctionRef\">Hole</Chunk>
+                        <Chunk type=\"NoRef\">( </Chunk>
+                        <Chunk type=\"FunctionRef\">LayoutNummer</Chunk>
+                        <Chunk type=\"NoRef\"> ) + 1</Chunk>
+                    </ChunkList>
+                </Calculation>
+            </Calculation>
+            ",
+        );
+
+        assert_eq!(calculation.text, "Hole( LayoutNummer ) + 1");
+        assert_eq!(
+            calculation.render(CalculationFormat::default()),
+            "Hole( LayoutNummer ) + 1"
+        );
+        assert_eq!(calculation.render(ENGLISH), "Get( LayoutNumber ) + 1");
+        assert_eq!(
+            calculation.chunks,
+            vec![
+                Chunk::FunctionRef("Hole".to_string()),
+                Chunk::NoRef("( ".to_string()),
+                Chunk::FunctionRef("LayoutNummer".to_string()),
+                Chunk::NoRef(" ) + 1".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_field_reference_chunks() {
+        let calculation = parse(
+            "
+            <Calculation>
+                <Text><![CDATA[IstLeer ( Foo::Länge ) UND Länge ( $text ) > 0]]></Text>
+                <ChunkList hash=\"00000000000000000000000000000000\">
+                    <Chunk type=\"FunctionRef\">IstLeer</Chunk>
+                    <Chunk type=\"NoRef\"> ( </Chunk>
+                    <Chunk type=\"FieldRef\">
+                        <FieldReference id=\"7\" name=\"Länge\" repetition=\"1\">
+                            <TableOccurrenceReference id=\"1065090\" name=\"Foo\"></TableOccurrenceReference>
+                        </FieldReference>
+                    </Chunk>
+                    <Chunk type=\"NoRef\"> ) UND </Chunk>
+                    <Chunk type=\"FunctionRef\">Länge</Chunk>
+                    <Chunk type=\"NoRef\"> ( </Chunk>
+                    <Chunk type=\"VariableReference\">$text</Chunk>
+                    <Chunk type=\"NoRef\"> ) &gt; 0</Chunk>
+                </ChunkList>
+            </Calculation>
+            ",
+        );
+
+        assert_eq!(
+            calculation.render(ENGLISH),
+            "IsEmpty ( Foo::Länge ) UND Length ( $text ) > 0"
+        );
+        assert_eq!(
+            calculation
+                .chunks
+                .iter()
+                .map(|chunk| chunk.text())
+                .collect::<String>(),
+            calculation.text
+        );
+
+        let field_reference = calculation
+            .chunks
+            .iter()
+            .find_map(|chunk| match chunk {
+                Chunk::FieldRef(reference, text) => Some((reference, text)),
+                _ => None,
+            })
+            .unwrap();
+        assert_eq!(field_reference.0.id, "7");
+        assert_eq!(field_reference.0.table_occurrence_id, "1065090");
+        assert_eq!(field_reference.1, "Foo::Länge");
+        assert!(calculation
+            .chunks
+            .contains(&Chunk::VariableReference("$text".to_string())));
+    }
+
+    #[test]
+    fn test_without_chunk_list() {
+        let calculation = parse(
+            "
+            <Calculation>
+                <Text><![CDATA[Hole ( ScriptParameter )]]></Text>
+            </Calculation>
+            ",
+        );
+
+        assert!(calculation.chunks.is_empty());
+        assert_eq!(calculation.render(ENGLISH), "Hole ( ScriptParameter )");
+    }
+
+    #[test]
+    fn test_function_names_in_literals() {
+        let calculation = parse(
+            "
+            <Calculation>
+                <Text><![CDATA[Wenn ( $a ; \"Wenn \\\"x\\\" NICHT\" ; <Funktion NICHT vorhanden> )]]></Text>
+                <ChunkList hash=\"00000000000000000000000000000000\">
+                    <Chunk type=\"FunctionRef\">Wenn</Chunk>
+                    <Chunk type=\"NoRef\"> ( </Chunk>
+                    <Chunk type=\"VariableReference\">$a</Chunk>
+                    <Chunk type=\"NoRef\"> ; \"</Chunk>
+                    <Chunk type=\"FunctionRef\">Wenn</Chunk>
+                    <Chunk type=\"NoRef\"> \\\"x\\\" </Chunk>
+                    <Chunk type=\"FunctionRef\">NICHT</Chunk>
+                    <Chunk type=\"NoRef\">\" ; &lt;Funktion </Chunk>
+                    <Chunk type=\"FunctionRef\">NICHT</Chunk>
+                    <Chunk type=\"NoRef\"> vorhanden&gt; )</Chunk>
+                </ChunkList>
+            </Calculation>
+            ",
+        );
+
+        assert_eq!(
+            calculation.render(ENGLISH),
+            "If ( $a ; \"Wenn \\\"x\\\" NICHT\" ; <Funktion NICHT vorhanden> )"
+        );
     }
 }
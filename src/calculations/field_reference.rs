@@ -1,11 +1,17 @@
+use crate::calculations::base_table_resolver::BaseTableResolver;
 use crate::calculations::calculation::Calculation;
 use crate::utils::attributes::{get_attribute, get_attributes};
 use quick_xml::events::{BytesStart, Event};
 use quick_xml::Reader;
 
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone, PartialEq)]
 pub struct FieldReference {
     pub field_reference: String,
+    pub id: String,
+    pub name: String,
+    pub repetition: String,
+    pub table_occurrence_id: String,
+    pub table_occurrence_name: String,
 }
 
 impl FieldReference {
@@ -13,6 +19,10 @@ impl FieldReference {
         let mut depth = 1;
         let mut item = FieldReference {
             field_reference: get_attribute(e, "name").unwrap_or_default(),
+            id: get_attribute(e, "id").unwrap_or_default(),
+            name: get_attribute(e, "name").unwrap_or_default(),
+            repetition: get_attribute(e, "repetition").unwrap_or_default(),
+            ..Default::default()
         };
 
         let mut buf: Vec<u8> = Vec::new();
@@ -22,7 +32,12 @@ impl FieldReference {
                 Ok(Event::Eof) => break,
                 Ok(Event::Start(e)) => {
                     depth += 1;
+                    if e.name().as_ref() == b"repetition" {
+                        item.repetition = get_attribute(&e, "value").unwrap_or_default();
+                    }
                     if e.name().as_ref() == b"TableOccurrenceReference" {
+                        item.table_occurrence_id = get_attribute(&e, "id").unwrap_or_default();
+                        item.table_occurrence_name = get_attribute(&e, "name").unwrap_or_default();
                         for attr in get_attributes(&e).unwrap() {
                             if attr.0 == "name" {
                                 match e.name().as_ref() {
@@ -66,7 +81,11 @@ impl FieldReference {
     }
 
     pub fn display(&self) -> Option<String> {
-        Some(self.field_reference.to_string())
+        match BaseTableResolver::annotation(&self.table_occurrence_id, &self.table_occurrence_name)
+        {
+            Some(base_table) => Some(format!("{} ({})", self.field_reference, base_table)),
+            None => Some(self.field_reference.to_string()),
+        }
     }
 }
 
@@ -93,6 +112,8 @@ impl FieldReferenceParameter {
                             .display()
                             .unwrap();
                         depth -= 1;
+                    } else if e.name().as_ref() == b"Variable" {
+                        item.field_reference = get_attribute(&e, "value").unwrap_or_default();
                     }
                 }
                 Ok(Event::End(_)) => {
@@ -120,7 +141,9 @@ impl FieldReferenceParameter {
 
 #[cfg(test)]
 mod tests {
+    use crate::calculations::base_table_resolver::BaseTableResolver;
     use crate::calculations::field_reference::{FieldReference, FieldReferenceParameter};
+    use crate::table_occurrence_catalog::TableOccurrence;
     use quick_xml::events::Event;
     use quick_xml::Reader;
 
@@ -137,13 +160,36 @@ mod tests {
             _ => panic!("Wrong read event"),
         };
 
-        let expected_output = "Foo::Bar".to_string();
+        let field_reference = FieldReference::from_xml(&mut reader, &element).unwrap();
+        assert_eq!(field_reference.display().unwrap(), "Foo::Bar".to_string());
+        assert_eq!(field_reference.id, "4");
+        assert_eq!(field_reference.repetition, "1");
+        assert_eq!(field_reference.table_occurrence_id, "1065090");
+        assert_eq!(field_reference.table_occurrence_name, "Foo");
+    }
+
+    #[test]
+    fn test_field_reference_with_base_table() {
+        let field_reference = FieldReference {
+            field_reference: "Invoices_Customers::Name".to_string(),
+            table_occurrence_id: "1065090".to_string(),
+            table_occurrence_name: "Invoices_Customers".to_string(),
+            ..Default::default()
+        };
+        let resolver = BaseTableResolver::new(&[TableOccurrence {
+            id: "1065090".to_string(),
+            base_table_id: "130".to_string(),
+            base_table_name: "Customers".to_string(),
+            ..Default::default()
+        }]);
+
         assert_eq!(
-            FieldReference::from_xml(&mut reader, &element)
-                .unwrap()
-                .display()
-                .unwrap(),
-            expected_output
+            resolver.apply(|| field_reference.display()),
+            Some("Invoices_Customers::Name (Customers)".to_string())
+        );
+        assert_eq!(
+            field_reference.display(),
+            Some("Invoices_Customers::Name".to_string())
         );
     }
 
@@ -171,4 +217,28 @@ mod tests {
             expected_output
         );
     }
+
+    #[test]
+    fn test_field_reference_parameter_variable() {
+        let xml_input = "<Parameter type=\"Target\">
+            <Variable value=\"$$my.result\">
+                <repetition value=\"1\"></repetition>
+            </Variable>
+        </Parameter>";
+
+        let mut reader = Reader::from_str(xml_input);
+        let element = match reader.read_event() {
+            Ok(Event::Start(e)) => e,
+            _ => panic!("Wrong read event"),
+        };
+
+        let expected_output = "$$my.result".to_string();
+        assert_eq!(
+            FieldReferenceParameter::from_xml(&mut reader, &element)
+                .unwrap()
+                .display()
+                .unwrap(),
+            expected_output
+        );
+    }
 }
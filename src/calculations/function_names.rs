@@ -0,0 +1,207 @@
+use clap::ValueEnum;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, ValueEnum)]
+pub enum FunctionLanguage {
+    /// Keep the function names of the exporting FileMaker client
+    #[default]
+    Source,
+    /// English function names
+    En,
+}
+
+/// German function names and their English names. Function names of exports in other
+/// languages are not translated.
+const FUNCTION_NAMES: [(&str, &str); 165] = [
+    ("Abschneiden", "Truncate"),
+    ("AktiveAusschnittZeileNr", "ActivePortalRowNumber"),
+    ("AktiveTextAuswahlGröße", "ActiveSelectionSize"),
+    ("AktiveTextAuswahlStart", "ActiveSelectionStart"),
+    ("AktivesFeldInhalt", "ActiveFieldContents"),
+    ("AktivesFeldName", "ActiveFieldName"),
+    ("AktivesFeldTabelle", "ActiveFieldTableName"),
+    ("AktivesLayoutobjektName", "ActiveLayoutObjectName"),
+    ("Anzahl", "Count"),
+    ("AnzahlDatensätzeGesamt", "TotalRecordCount"),
+    ("AnzahlGefundeneDatensätze", "FoundCount"),
+    ("Austauschen", "Substitute"),
+    ("Auswahl", "Choose"),
+    ("Berechne", "Evaluate"),
+    ("BerechnungsFehler", "EvaluationError"),
+    ("BildschirmBreite", "ScreenWidth"),
+    ("BildschirmHöhe", "ScreenHeight"),
+    ("DateiGebietsschemaElemente", "FileLocaleElements"),
+    ("DateiGröße", "FileSize"),
+    ("DateiName", "FileName"),
+    ("DateiPfad", "FilePath"),
+    ("Datum", "Date"),
+    ("DatensatzID", "RecordID"),
+    ("DatensatzÄnderungenAnzahl", "RecordModificationCount"),
+    ("DatensatzPositionInErgebnismenge", "RecordNumber"),
+    ("DesktopPfad", "DesktopPath"),
+    ("DokumentenPfad", "DocumentsPath"),
+    ("ElementeAnzahl", "ValueCount"),
+    ("ElementeLinks", "LeftValues"),
+    ("ElementeMitte", "MiddleValues"),
+    ("ElementeRechts", "RightValues"),
+    ("Ersetzen", "Replace"),
+    ("Exakt", "Exact"),
+    ("Falls", "Case"),
+    ("Falsch", "False"),
+    ("FeldBemerkung", "FieldComment"),
+    ("FeldLayoutStil", "FieldStyle"),
+    ("FeldNamen", "FieldNames"),
+    ("FeldTyp", "FieldType"),
+    ("FensterBreite", "WindowWidth"),
+    ("Fensterbreite", "WindowWidth"),
+    ("FensterHöhe", "WindowHeight"),
+    ("Fensterhöhe", "WindowHeight"),
+    ("FensterInhaltBreite", "WindowContentWidth"),
+    ("FensterInhaltHöhe", "WindowContentHeight"),
+    ("FensterLinks", "WindowLeft"),
+    ("Fenstermodus", "WindowMode"),
+    ("FensterName", "WindowName"),
+    ("Fensternamen", "WindowNames"),
+    ("FensterOben", "WindowTop"),
+    ("FensterSchreibtischBreite", "WindowDesktopWidth"),
+    ("FensterSchreibtischHöhe", "WindowDesktopHeight"),
+    ("FilterElemente", "FilterValues"),
+    ("FilterZeichen", "Filter"),
+    ("FortlNrInTextÄndern", "SerialIncrement"),
+    ("Ganzzahl", "Int"),
+    ("Gerät", "Device"),
+    ("Großbuchstaben", "Upper"),
+    ("GroßKleinbuchstaben", "Proper"),
+    ("Hole", "Get"),
+    ("HoleContainerAttribute", "GetContainerAttribute"),
+    ("HoleFeldname", "GetFieldName"),
+    ("HoleFeldwert", "GetField"),
+    ("HoleNtenDatensatz", "GetNthRecord"),
+    ("HoleWert", "GetValue"),
+    ("HoleWiederholung", "GetRepetition"),
+    ("HostIPAdresse", "HostIPAddress"),
+    ("HostName", "HostName"),
+    ("HostZeitstempel", "CurrentHostTimestamp"),
+    ("IstGültig", "IsValid"),
+    ("IstGültigerAusdruck", "IsValidExpression"),
+    ("IstLeer", "IsEmpty"),
+    ("Jahreszahl", "Year"),
+    ("KalendertagImJahr", "DayOfYear"),
+    ("KalendertagZahl", "Day"),
+    ("KalenderwocheZahl", "WeekOfYear"),
+    ("Kleinbuchstaben", "Lower"),
+    ("KontoBerechtigungenName", "AccountPrivilegeSetName"),
+    ("KontoErweiterteZugriffsrechte", "AccountExtendedPrivileges"),
+    ("Kontoname", "AccountName"),
+    ("LayoutAnzeigeAlsStatus", "LayoutViewState"),
+    ("LayoutAnzahl", "LayoutCount"),
+    ("LayoutNamen", "LayoutNames"),
+    ("LayoutNummer", "LayoutNumber"),
+    ("LayoutobjektNamen", "LayoutObjectNames"),
+    ("LayoutTabellenname", "LayoutTableName"),
+    ("Letzte", "Last"),
+    ("LetzteFehlerNr", "LastError"),
+    ("LetzteFehlerPosition", "LastErrorLocation"),
+    ("LetzteMeldungswahl", "LastMessageChoice"),
+    ("Länge", "Length"),
+    ("Lg", "Log"),
+    ("LiesAlsBoolean", "GetAsBoolean"),
+    ("LiesAlsCSS", "GetAsCSS"),
+    ("LiesAlsDatum", "GetAsDate"),
+    ("LiesAlsSVG", "GetAsSVG"),
+    ("LiesAlsText", "GetAsText"),
+    ("LiesAlsURLVerschlüsselt", "GetAsURLEncoded"),
+    ("LiesAlsZahl", "GetAsNumber"),
+    ("LiesAlsZeit", "GetAsTime"),
+    ("LiesAlsZeitstempel", "GetAsTimestamp"),
+    ("LiesLayoutobjektAttribut", "GetLayoutObjectAttribute"),
+    ("Liste", "List"),
+    ("Minuten", "Minute"),
+    ("Mittelwert", "Average"),
+    ("MonatName", "MonthName"),
+    ("MonatZahl", "Month"),
+    ("MusterAnzahl", "PatternCount"),
+    ("Nachschlagen", "Lookup"),
+    ("NachschlagenNächste", "LookupNext"),
+    ("NächsteGrößereGanzzahl", "Ceiling"),
+    ("NächsteKleinereGanzzahl", "Floor"),
+    ("ProgrammBenutzername", "UserName"),
+    ("ProgrammSprache", "ApplicationLanguage"),
+    ("ProgrammVersion", "ApplicationVersion"),
+    ("Runden", "Round"),
+    ("ScriptErgebnis", "ScriptResult"),
+    ("ScriptNamen", "ScriptNames"),
+    ("Sekunden", "Seconds"),
+    ("Selbst", "Self"),
+    ("SetzeVar", "Let"),
+    ("Solange", "While"),
+    ("SonderTastenGedrückt", "ActiveModifierKeys"),
+    ("Sortierstatus", "SortState"),
+    ("SQLAusführen", "ExecuteSQL"),
+    ("StdAbw", "StDev"),
+    ("Stunden", "Hour"),
+    ("Summe", "Sum"),
+    ("SystemDarstellung", "SystemAppearance"),
+    ("SystemDatum", "CurrentDate"),
+    ("SystemGebietsschemaElemente", "SystemLocaleElements"),
+    ("SystemIPAdresse", "SystemIPAddress"),
+    ("SystemLaufwerk", "SystemDrive"),
+    ("SystemPlattform", "SystemPlatform"),
+    ("SystemSprache", "SystemLanguage"),
+    ("SystemUhrzeit", "CurrentTime"),
+    (
+        "SystemUhrzeitUTCMillisekunden",
+        "CurrentTimeUTCMilliseconds",
+    ),
+    ("SystemZeitstempel", "CurrentTimestamp"),
+    ("TabellenNamen", "TableNames"),
+    ("TemporärerPfad", "TemporaryPath"),
+    ("TextFarbe", "TextColor"),
+    ("TextFormatEntfernen", "TextFormatRemove"),
+    ("TextGröße", "TextSize"),
+    ("TextStilEntfernen", "TextStyleRemove"),
+    ("TextStilZufügen", "TextStyleAdd"),
+    ("Trimme", "Trim"),
+    ("TrimmeAlle", "TrimAll"),
+    ("Vorzeichen", "Sign"),
+    ("Wahr", "True"),
+    ("Wenn", "If"),
+    ("WertelisteEinträge", "ValueListItems"),
+    ("WochentagName", "DayName"),
+    ("WochentagZahl", "DayOfWeek"),
+    ("Wurzel", "Sqrt"),
+    ("WörterAnzahl", "WordCount"),
+    ("WörterLinks", "LeftWords"),
+    ("WörterMitte", "MiddleWords"),
+    ("WörterRechts", "RightWords"),
+    ("ZeichenLinks", "Left"),
+    ("ZeichenMitte", "Middle"),
+    ("ZeichenRechts", "Right"),
+    ("Zeit", "Time"),
+    ("ZeitStempel", "Timestamp"),
+    ("Zitat", "Quote"),
+    ("Zufall", "Random"),
+];
+
+pub fn canonical_function_name(name: &str) -> &str {
+    FUNCTION_NAMES
+        .iter()
+        .find(|(localized, _)| *localized == name)
+        .map(|(_, canonical)| *canonical)
+        .unwrap_or(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_canonical_function_name() {
+        assert_eq!(canonical_function_name("Hole"), "Get");
+        assert_eq!(
+            canonical_function_name("AnzahlGefundeneDatensätze"),
+            "FoundCount"
+        );
+        assert_eq!(canonical_function_name("JSONGetElement"), "JSONGetElement");
+        assert_eq!(canonical_function_name("Get"), "Get");
+    }
+}
@@ -50,7 +50,6 @@ impl LayoutReferenceContainer {
                     match e.name().as_ref() {
                         b"LayoutReference" => {
                             item.layout_reference = get_attribute(&e, "name").unwrap().to_string();
-                            break;
                         }
                         b"Label" => {
                             item.layout_reference =
@@ -142,6 +141,28 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_layout_consumes_container() {
+        let xml_input = "\
+        <LayoutReferenceContainer value=\"5\">\
+            <LayoutReference id=\"2\" name=\"Aufgabenliste\"></LayoutReference>\
+        </LayoutReferenceContainer>\
+        <Animation name=\"Ohne\" value=\"0\"></Animation>";
+
+        let mut reader = Reader::from_str(xml_input);
+        let element = match reader.read_event() {
+            Ok(Event::Start(e)) => e.into_owned(),
+            _ => panic!("Wrong read event"),
+        };
+        LayoutReferenceContainer::from_xml(&mut reader, &element).unwrap();
+
+        // Go to Layout reads its Animation parameter after the container
+        match reader.read_event() {
+            Ok(Event::Start(e)) => assert_eq!(e.name().as_ref(), b"Animation"),
+            _ => panic!("Wrong read event"),
+        };
+    }
+
     #[test]
     fn test_by_name() {
         let xml_input = "
@@ -1,3 +1,8 @@
+pub(crate) mod base_table_resolver;
 pub(crate) mod calculation;
 pub(crate) mod field_reference;
+pub(crate) mod function_names;
 pub(crate) mod layout_reference;
+pub(crate) mod path_list;
+pub(crate) mod pretty_print;
+pub(crate) mod window_reference;
@@ -0,0 +1,96 @@
+use crate::utils;
+use crate::utils::attributes::get_attribute;
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+
+#[derive(Debug, Default)]
+pub struct UniversalPathList {
+    pub auto_open: bool,
+    pub create_mail: bool,
+    pub paths: Vec<String>,
+}
+
+impl UniversalPathList {
+    pub fn from_xml(reader: &mut Reader<&[u8]>, e: &BytesStart) -> Result<Self, String> {
+        let mut depth = 1;
+        let mut in_location = false;
+        let mut item = UniversalPathList {
+            auto_open: get_attribute(e, "AutoOpen").unwrap_or_default() == "True",
+            create_mail: get_attribute(e, "CreateMail").unwrap_or_default() == "True",
+            ..Default::default()
+        };
+
+        let mut buf: Vec<u8> = Vec::new();
+        loop {
+            match reader.read_event_into(&mut buf) {
+                Err(_) => continue,
+                Ok(Event::Eof) => break,
+                Ok(Event::Start(e)) => {
+                    depth += 1;
+                    if e.name().as_ref() == b"Location" {
+                        in_location = true;
+                    }
+                }
+                Ok(Event::CData(e)) if in_location => {
+                    item.paths.push(utils::xml_utils::cdata_to_string(&e));
+                }
+                Ok(Event::End(e)) => {
+                    depth -= 1;
+                    if depth == 0 {
+                        break;
+                    }
+
+                    if e.name().as_ref() == b"Location" {
+                        in_location = false;
+                    }
+                }
+                _ => {}
+            }
+            buf.clear();
+        }
+
+        Ok(item)
+    }
+
+    pub fn display(&self) -> Option<String> {
+        Some(
+            self.paths
+                .iter()
+                .map(|path| format!("\"{}\"", path))
+                .collect::<Vec<_>>()
+                .join(", "),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_path_list() {
+        let xml_input = "
+        <UniversalPathList fileType=\"TABS\" AutoOpen=\"True\" CreateMail=\"False\" membercount=\"2\">
+            <ObjectList>
+                <Location><![CDATA[file:file.txt]]></Location>
+                <Location><![CDATA[$Path]]></Location>
+            </ObjectList>
+        </UniversalPathList>
+        ";
+
+        let mut reader = Reader::from_str(xml_input);
+        reader.trim_text(true);
+        let element = match reader.read_event() {
+            Ok(Event::Start(e)) => e,
+            _ => panic!("Wrong read event"),
+        };
+
+        let path_list = UniversalPathList::from_xml(&mut reader, &element).unwrap();
+        assert!(path_list.auto_open);
+        assert!(!path_list.create_mail);
+        assert_eq!(
+            path_list.display(),
+            Some("\"file:file.txt\", \"$Path\"".to_string())
+        );
+    }
+}
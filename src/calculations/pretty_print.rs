@@ -0,0 +1,429 @@
+use crate::calculations::calculation::{Calculation, CalculationFormat, Chunk};
+use crate::calculations::function_names::canonical_function_name;
+
+const ALWAYS_BREAK: [&str; 4] = ["Let", "Case", "If", "While"];
+const MAX_INLINE_LENGTH: usize = 80;
+
+#[derive(Debug, PartialEq)]
+enum Token {
+    Function(String),
+    Reference(String),
+    Atom(String),
+    Operator(String),
+    Open(char),
+    Close(char),
+    Separator,
+    LineComment(String),
+    BlockComment(String),
+}
+
+#[derive(Debug)]
+enum Node {
+    Atom(String),
+    LineComment(String),
+    Group {
+        name: Option<String>,
+        open: char,
+        args: Vec<Vec<Node>>,
+    },
+}
+
+/// Formats a calculation with one argument per line for `Let`, `Case`, `If` and `While` and for
+/// long JSON function calls. Whitespace between tokens is normalized to a single space, so
+/// whitespace-only changes don't show up in the output. Returns the unformatted calculation if
+/// it can't be parsed.
+pub fn pretty_print(calculation: &Calculation, format: CalculationFormat) -> String {
+    let unformatted = || {
+        calculation.render(CalculationFormat {
+            pretty_print: false,
+            ..format
+        })
+    };
+    let tokens = match tokenize(calculation, format) {
+        Some(tokens) => tokens,
+        None => return unformatted(),
+    };
+
+    let mut position = 0;
+    match parse(&tokens, &mut position, None) {
+        Some(mut args) if args.len() == 1 => render_nodes(&args.remove(0), 0),
+        _ => unformatted(),
+    }
+}
+
+fn tokenize(calculation: &Calculation, format: CalculationFormat) -> Option<Vec<Token>> {
+    let mut tokens: Vec<Token> = Vec::new();
+    let chunks = if calculation.chunks.is_empty() {
+        vec![Chunk::NoRef(calculation.text.clone())]
+    } else {
+        calculation.formatted_chunks(format)
+    };
+
+    let mut attached = false;
+    for chunk in &chunks {
+        match chunk {
+            Chunk::FieldRef(_, text) if text.is_empty() => return None,
+            Chunk::FunctionRef(_) | Chunk::CustomFunctionRef(_) => {
+                tokens.push(Token::Function(chunk.text().to_string()));
+                attached = false;
+            }
+            Chunk::FieldRef(..) | Chunk::VariableReference(_) => {
+                tokens.push(Token::Reference(chunk.text().to_string()));
+                attached = true;
+            }
+            Chunk::NoRef(text) | Chunk::Other(text) => {
+                tokenize_text(text, &mut tokens, attached)?;
+                attached = false;
+            }
+        }
+    }
+
+    Some(tokens)
+}
+
+fn tokenize_text(text: &str, tokens: &mut Vec<Token>, attached: bool) -> Option<()> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut atom = String::new();
+    let mut index = 0;
+
+    while index < chars.len() {
+        let char = chars[index];
+        let next = chars.get(index + 1).copied().unwrap_or_default();
+
+        if char == '[' && (!atom.is_empty() || (index == 0 && attached)) {
+            // repetition of a field or variable, e.g. `$list[2]`
+            let end = index + chars[index..].iter().position(|c| *c == ']')?;
+            let repetition: String = chars[index..=end].iter().collect();
+            match tokens.last_mut() {
+                _ if !atom.is_empty() => atom.push_str(&repetition),
+                Some(Token::Reference(reference)) => reference.push_str(&repetition),
+                _ => return None,
+            }
+            index = end + 1;
+            continue;
+        }
+
+        let token = match char {
+            '"' => {
+                let mut end = index + 1;
+                while end < chars.len() && chars[end] != '"' {
+                    if chars[end] == '\\' {
+                        end += 1;
+                    }
+                    end += 1;
+                }
+                if end >= chars.len() {
+                    return None;
+                }
+                atom.extend(&chars[index..=end]);
+                index = end + 1;
+                continue;
+            }
+            '/' if next == '*' => {
+                let end = (index + 2..chars.len().saturating_sub(1))
+                    .find(|end| chars[*end] == '*' && chars[*end + 1] == '/')?;
+                let comment: String = chars[index..end + 2].iter().collect();
+                index = end + 2;
+                Some(Token::BlockComment(comment))
+            }
+            '/' if next == '/' => {
+                let end = (index..chars.len())
+                    .find(|end| chars[*end] == '\r' || chars[*end] == '\n')
+                    .unwrap_or(chars.len());
+                let comment: String = chars[index..end].iter().collect();
+                index = end;
+                Some(Token::LineComment(comment.trim_end().to_string()))
+            }
+            '$' if next == '{' && atom.is_empty() => {
+                // quoted name, e.g. `${Price-Net}`
+                let end = index + chars[index..].iter().position(|c| *c == '}')?;
+                atom.extend(&chars[index..=end]);
+                index = end + 1;
+                continue;
+            }
+            '-' if atom.is_empty() && expects_operand(tokens) => {
+                // sign of a number, e.g. `-1`
+                atom.push(char);
+                index += 1;
+                continue;
+            }
+            '<' | '>' if next == '=' || (char == '<' && next == '>') => {
+                index += 2;
+                Some(Token::Operator(format!("{}{}", char, next)))
+            }
+            '&' | '+' | '-' | '*' | '/' | '^' | '=' | '≠' | '<' | '>' | '≤' | '≥' => {
+                index += 1;
+                Some(Token::Operator(char.to_string()))
+            }
+            '(' | '[' => {
+                index += 1;
+                Some(Token::Open(char))
+            }
+            ')' | ']' => {
+                index += 1;
+                Some(Token::Close(char))
+            }
+            ';' => {
+                index += 1;
+                Some(Token::Separator)
+            }
+            _ if char.is_whitespace() => {
+                index += 1;
+                None
+            }
+            _ => {
+                atom.push(char);
+                index += 1;
+                continue;
+            }
+        };
+
+        if !atom.is_empty() {
+            tokens.push(Token::Atom(std::mem::take(&mut atom)));
+        }
+        if let Some(token) = token {
+            tokens.push(token);
+        }
+    }
+    if !atom.is_empty() {
+        tokens.push(Token::Atom(atom));
+    }
+
+    Some(())
+}
+
+fn expects_operand(tokens: &[Token]) -> bool {
+    matches!(
+        tokens.last(),
+        None | Some(
+            Token::Open(_)
+                | Token::Separator
+                | Token::Operator(_)
+                | Token::LineComment(_)
+                | Token::BlockComment(_)
+        )
+    )
+}
+
+fn parse(tokens: &[Token], position: &mut usize, closing: Option<char>) -> Option<Vec<Vec<Node>>> {
+    let mut args: Vec<Vec<Node>> = vec![Vec::new()];
+
+    while *position < tokens.len() {
+        let token = &tokens[*position];
+        *position += 1;
+
+        let current = args.last_mut()?;
+        match token {
+            Token::Function(name)
+            | Token::Reference(name)
+            | Token::Atom(name)
+            | Token::Operator(name) => current.push(Node::Atom(name.to_string())),
+            Token::BlockComment(comment) => current.push(Node::Atom(comment.to_string())),
+            Token::LineComment(comment) => current.push(Node::LineComment(comment.to_string())),
+            Token::Separator if closing.is_some() => args.push(Vec::new()),
+            Token::Separator => return None,
+            Token::Open(open) => {
+                let name = match (open, &tokens[..*position - 1]) {
+                    ('(', [.., Token::Function(name)]) => Some(name.to_string()),
+                    ('(', [.., Token::Atom(name)]) if is_identifier(name) => Some(name.to_string()),
+                    _ => None,
+                };
+                if name.is_some() {
+                    current.pop();
+                }
+                let close = if *open == '(' { ')' } else { ']' };
+                let group_args = parse(tokens, position, Some(close))?;
+                args.last_mut()?.push(Node::Group {
+                    name,
+                    open: *open,
+                    args: group_args,
+                });
+            }
+            Token::Close(close) if Some(*close) == closing => return Some(args),
+            Token::Close(_) => return None,
+        }
+    }
+
+    match closing {
+        None => Some(args),
+        Some(_) => None,
+    }
+}
+
+fn is_identifier(text: &str) -> bool {
+    text.chars()
+        .next()
+        .is_some_and(|char| char.is_alphabetic() || char == '_')
+        && text
+            .chars()
+            .all(|char| char.is_alphanumeric() || char == '_' || char == '.')
+}
+
+fn render_nodes(nodes: &[Node], indent: usize) -> String {
+    let mut rendered = String::new();
+    let mut after_line_comment = false;
+
+    for node in nodes {
+        if after_line_comment {
+            rendered.push('\r');
+            rendered.push_str(&"\t".repeat(indent));
+        } else if !rendered.is_empty() {
+            rendered.push(' ');
+        }
+
+        after_line_comment = false;
+        match node {
+            Node::Atom(text) => rendered.push_str(text),
+            Node::LineComment(comment) => {
+                rendered.push_str(comment);
+                after_line_comment = true;
+            }
+            Node::Group { name, open, args } => {
+                rendered.push_str(&render_group(name.as_deref(), *open, args, indent, false))
+            }
+        }
+    }
+    if after_line_comment {
+        rendered.push('\r');
+        rendered.push_str(&"\t".repeat(indent));
+    }
+
+    rendered
+}
+
+fn render_group(
+    name: Option<&str>,
+    open: char,
+    args: &[Vec<Node>],
+    indent: usize,
+    force_break: bool,
+) -> String {
+    let close = if open == '(' { ')' } else { ']' };
+    let prefix = match name {
+        Some(name) => format!("{} {}", name, open),
+        None => open.to_string(),
+    };
+    if args.len() == 1 && args[0].is_empty() {
+        return format!("{}{}", prefix, close);
+    }
+
+    let inline = format!(
+        "{} {} {}",
+        prefix,
+        args.iter()
+            .map(|arg| render_nodes(arg, indent))
+            .collect::<Vec<_>>()
+            .join(" ; "),
+        close
+    );
+
+    let name = name.map(canonical_function_name);
+    let is_let = name.is_some_and(|name| name.eq_ignore_ascii_case("Let"));
+    let break_group = force_break
+        || match name {
+            Some(name) if ALWAYS_BREAK.iter().any(|n| n.eq_ignore_ascii_case(name)) => true,
+            Some(name) if name.starts_with("JSON") => {
+                inline.len() > MAX_INLINE_LENGTH || inline.contains('\r')
+            }
+            _ => false,
+        };
+    if !break_group {
+        return inline;
+    }
+
+    let inner_indent = "\t".repeat(indent + 1);
+    let mut rendered = prefix;
+    for (index, arg) in args.iter().enumerate() {
+        rendered.push('\r');
+        rendered.push_str(&inner_indent);
+        match arg.as_slice() {
+            [Node::Group { name, open, args }] if is_let && index == 0 && *open == '[' => rendered
+                .push_str(&render_group(
+                    name.as_deref(),
+                    *open,
+                    args,
+                    indent + 1,
+                    true,
+                )),
+            _ => rendered.push_str(&render_nodes(arg, indent + 1)),
+        }
+        if index + 1 < args.len() {
+            rendered.push_str(" ;");
+        }
+    }
+    rendered.push('\r');
+    rendered.push_str(&"\t".repeat(indent));
+    rendered.push(close);
+
+    rendered
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pretty_print_text(text: &str) -> String {
+        pretty_print(
+            &Calculation::new(text.to_string(), Vec::new()),
+            CalculationFormat::default(),
+        )
+    }
+
+    #[test]
+    fn test_inline_whitespace_is_normalized() {
+        assert_eq!(
+            pretty_print_text("Get(ScriptParameter)   &  \"a  b\""),
+            "Get ( ScriptParameter ) & \"a  b\""
+        );
+    }
+
+    #[test]
+    fn test_let_and_if() {
+        assert_eq!(
+            pretty_print_text(
+                "Let ( [ ~a = 1 ; ~b = If ( $x ; 2 ; 3 ) ] ; ~a + ~b )"
+            ),
+            "Let (\r\t[\r\t\t~a = 1 ;\r\t\t~b = If (\r\t\t\t$x ;\r\t\t\t2 ;\r\t\t\t3\r\t\t)\r\t] ;\r\t~a + ~b\r)"
+        );
+    }
+
+    #[test]
+    fn test_short_json_function_stays_inline() {
+        assert_eq!(
+            pretty_print_text("JSONGetElement ( $json ; \"id\" )"),
+            "JSONGetElement ( $json ; \"id\" )"
+        );
+    }
+
+    #[test]
+    fn test_long_json_function_breaks() {
+        assert_eq!(
+            pretty_print_text(
+                "JSONSetElement ( \"{}\" ; [ \"first_name\" ; $firstName ; JSONString ] ; [ \"last_name\" ; $lastName ; JSONString ] )"
+            ),
+            "JSONSetElement (\r\t\"{}\" ;\r\t[ \"first_name\" ; $firstName ; JSONString ] ;\r\t[ \"last_name\" ; $lastName ; JSONString ]\r)"
+        );
+    }
+
+    #[test]
+    fn test_comments_and_repetitions() {
+        assert_eq!(
+            pretty_print_text("Case ( // first\r$list[2] = \"a\" ; 1 ; /* default */ 0 )"),
+            "Case (\r\t// first\r\t$list[2] = \"a\" ;\r\t1 ;\r\t/* default */ 0\r)"
+        );
+    }
+
+    #[test]
+    fn test_operators_are_normalized() {
+        assert_eq!(pretty_print_text("a&b"), pretty_print_text("a & b"));
+        assert_eq!(
+            pretty_print_text("Abs(-1)*(2-x)<>\"a-b\"&${Price-Net}>=-$y"),
+            "Abs ( -1 ) * ( 2 - x ) <> \"a-b\" & ${Price-Net} >= -$y"
+        );
+    }
+
+    #[test]
+    fn test_unbalanced_calculation_is_unchanged() {
+        assert_eq!(pretty_print_text("If ( $a ; 1"), "If ( $a ; 1");
+    }
+}
@@ -0,0 +1,153 @@
+use crate::calculations::calculation::Calculation;
+use crate::utils::attributes::get_attribute;
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+
+#[derive(Debug, Default)]
+pub struct WindowBounds {
+    pub height: String,
+    pub width: String,
+    pub top: String,
+    pub left: String,
+}
+
+#[derive(Debug, Default)]
+pub struct WindowReference {
+    pub select_kind: String,
+    pub name: String,
+    pub current_file: bool,
+    pub rename: String,
+    pub bounds: WindowBounds,
+}
+
+impl WindowReference {
+    pub fn from_xml(reader: &mut Reader<&[u8]>, _: &BytesStart) -> Result<Self, String> {
+        let mut depth = 1;
+        let mut item = WindowReference::default();
+
+        let mut buf: Vec<u8> = Vec::new();
+        loop {
+            match reader.read_event_into(&mut buf) {
+                Err(_) => continue,
+                Ok(Event::Eof) => break,
+                Ok(Event::Start(e)) => {
+                    depth += 1;
+                    match e.name().as_ref() {
+                        b"Select" => {
+                            item.select_kind = get_attribute(&e, "kind").unwrap_or_default();
+                        }
+                        b"Name" => {
+                            item.current_file =
+                                get_attribute(&e, "current").unwrap_or_default() == "True";
+                            item.name = Calculation::from_xml(reader, &e).unwrap();
+                            depth -= 1;
+                        }
+                        b"Rename" => {
+                            item.rename = Calculation::from_xml(reader, &e).unwrap();
+                            depth -= 1;
+                        }
+                        b"height" => {
+                            item.bounds.height = Calculation::from_xml(reader, &e).unwrap();
+                            depth -= 1;
+                        }
+                        b"width" => {
+                            item.bounds.width = Calculation::from_xml(reader, &e).unwrap();
+                            depth -= 1;
+                        }
+                        b"top" => {
+                            item.bounds.top = Calculation::from_xml(reader, &e).unwrap();
+                            depth -= 1;
+                        }
+                        b"left" => {
+                            item.bounds.left = Calculation::from_xml(reader, &e).unwrap();
+                            depth -= 1;
+                        }
+                        _ => {}
+                    }
+                }
+                Ok(Event::End(_)) => {
+                    depth -= 1;
+                    if depth == 0 {
+                        break;
+                    }
+                }
+                _ => {}
+            }
+            buf.clear();
+        }
+
+        Ok(item)
+    }
+
+    pub fn display(&self) -> Option<String> {
+        match self.select_kind.as_str() {
+            "0" => Some("Current window".to_string()),
+            _ if self.current_file => Some(format!("Name: {} ; Current file", self.name)),
+            _ => Some(format!("Name: {}", self.name)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_current_window() {
+        let xml_input = "
+        <WindowReference>
+            <Select kind=\"0\" type=\"current\"></Select>
+        </WindowReference>
+        ";
+
+        let mut reader = Reader::from_str(xml_input);
+        reader.trim_text(true);
+        let element = match reader.read_event() {
+            Ok(Event::Start(e)) => e,
+            _ => panic!("Wrong read event"),
+        };
+
+        let expected_output = Some("Current window".to_string());
+        assert_eq!(
+            WindowReference::from_xml(&mut reader, &element)
+                .unwrap()
+                .display(),
+            expected_output
+        );
+    }
+
+    #[test]
+    fn test_window_by_name() {
+        let xml_input = "
+        <WindowReference>
+            <Select kind=\"1\" type=\"Calculated\">
+                <Name current=\"True\">
+                    <Calculation datatype=\"1\" position=\"0\">
+                        <Calculation>
+                            <Text><![CDATA[$FensterName]]></Text>
+                            <ChunkList hash=\"F4A6E2F3D5D9CF5D1B44D2ED0D7D2F85\">
+                                <Chunk type=\"VariableReference\">$FensterName</Chunk>
+                            </ChunkList>
+                        </Calculation>
+                    </Calculation>
+                </Name>
+            </Select>
+        </WindowReference>
+        ";
+
+        let mut reader = Reader::from_str(xml_input);
+        reader.trim_text(true);
+        let element = match reader.read_event() {
+            Ok(Event::Start(e)) => e,
+            _ => panic!("Wrong read event"),
+        };
+
+        let expected_output = Some("Name: $FensterName ; Current file".to_string());
+        assert_eq!(
+            WindowReference::from_xml(&mut reader, &element)
+                .unwrap()
+                .display(),
+            expected_output
+        );
+    }
+}
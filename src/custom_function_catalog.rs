@@ -1,11 +1,14 @@
-use crate::{escape_filename, join_scope_id_and_name};
+use crate::broken_references::broken_reference;
+use crate::calculations::calculation::{Calculation, CalculationFormat, Chunk};
+use crate::references::{describe_location, References};
+use crate::{escape_filename, join_scope_id_and_name, Settings};
 use quick_xml::events::{BytesStart, Event};
 use quick_xml::reader::Reader;
 use std::io::{BufRead, Read};
 use std::path::Path;
 
-use crate::utils::attributes::get_attributes;
-use crate::utils::xml_utils::{cdata_to_string, local_name_to_string};
+use crate::utils::attributes::{get_attribute, get_attributes};
+use crate::utils::xml_utils::{cdata_to_string, local_name_to_string, text_to_string};
 use crate::utils::{initialize_out_dir, write_text_file, Entity};
 
 pub fn xml_explode_custom_function_catalog<R: Read + BufRead>(
@@ -13,11 +16,16 @@ pub fn xml_explode_custom_function_catalog<R: Read + BufRead>(
     _: &BytesStart,
     out_dir_path: &Path,
     fm_file_name: &str,
-) {
+    settings: &Settings,
+) -> References {
     let out_dir_path = out_dir_path.join("custom_functions").join(fm_file_name);
     initialize_out_dir(&out_dir_path);
 
     let mut custom_function_info = Entity::default();
+    let mut chunks: Vec<Chunk> = Vec::new();
+    let mut in_chunk = false;
+
+    let mut references = References::default();
 
     let mut depth = 1;
     let mut buf = Vec::new();
@@ -33,6 +41,13 @@ pub fn xml_explode_custom_function_catalog<R: Read + BufRead>(
                     continue;
                 } else if depth == 3 {
                     custom_function_info.clear();
+                    chunks.clear();
+                } else if e.name().as_ref() == b"Chunk" {
+                    in_chunk = true;
+                    chunks.push(Chunk::new(
+                        get_attribute(&e, "type").unwrap_or_default().as_str(),
+                        String::new(),
+                    ));
                 } else if depth == 4 && e.name().as_ref() == b"CustomFunctionReference" {
                     for attr in get_attributes(&e).unwrap() {
                         match attr.0.as_str() {
@@ -41,10 +56,42 @@ pub fn xml_explode_custom_function_catalog<R: Read + BufRead>(
                             _ => {}
                         }
                     }
+                } else if let Some((reference_type, reference)) = broken_reference(&e) {
+                    references.add_broken(
+                        "Custom function",
+                        &join_scope_id_and_name(
+                            &custom_function_info.id,
+                            &custom_function_info.name,
+                        ),
+                        &[],
+                        None,
+                        reference_type,
+                        reference,
+                    );
+                }
+
+                if let (true, Some(Chunk::FieldRef(reference, _))) = (in_chunk, chunks.last_mut()) {
+                    match e.name().as_ref() {
+                        b"FieldReference" => {
+                            reference.id = get_attribute(&e, "id").unwrap_or_default();
+                            reference.name = get_attribute(&e, "name").unwrap_or_default();
+                        }
+                        b"TableOccurrenceReference" => {
+                            reference.table_occurrence_id =
+                                get_attribute(&e, "id").unwrap_or_default();
+                            reference.table_occurrence_name =
+                                get_attribute(&e, "name").unwrap_or_default();
+                        }
+                        _ => {}
+                    }
                 }
             }
             Ok(Event::End(e)) => {
                 depth -= 1;
+                if e.name().as_ref() == b"Chunk" {
+                    in_chunk = false;
+                }
+
                 if depth == 0 {
                     break;
                 } else if depth < 2 {
@@ -52,6 +99,27 @@ pub fn xml_explode_custom_function_catalog<R: Read + BufRead>(
                 } else if depth == 2
                     && local_name_to_string(e.name().as_ref()) == "CustomFunctionCalc"
                 {
+                    let location = describe_location(
+                        "Custom function",
+                        &join_scope_id_and_name(
+                            &custom_function_info.id,
+                            &custom_function_info.name,
+                        ),
+                        &[],
+                        None,
+                    );
+                    for chunk in &chunks {
+                        if let Chunk::FieldRef(reference, _) = chunk {
+                            references.add_field(reference.clone(), location.to_string());
+                        }
+                    }
+
+                    let calculation = Calculation::new(
+                        std::mem::take(&mut custom_function_info.content),
+                        std::mem::take(&mut chunks),
+                    );
+                    custom_function_info.content =
+                        calculation.render(CalculationFormat::new(settings));
                     write_custom_function_to_file(&out_dir_path, &custom_function_info);
                     custom_function_info.clear();
                 }
@@ -65,11 +133,18 @@ pub fn xml_explode_custom_function_catalog<R: Read + BufRead>(
                     .content
                     .push_str(cdata_to_string(&e).as_str());
             }
+            Ok(Event::Text(e)) if in_chunk => {
+                if let Some(chunk) = chunks.last_mut() {
+                    chunk.set_text(text_to_string(&e));
+                }
+            }
             _ => {}
         }
 
         buf.clear()
     }
+
+    references
 }
 
 fn write_custom_function_to_file(output_dir: &Path, cf: &Entity) {
@@ -3,18 +3,21 @@ use quick_xml::reader::Reader;
 use std::io::{BufRead, Read};
 use std::path::Path;
 
+use crate::references::References;
 use crate::utils::xml_utils::skip_element;
-use crate::utils::{initialize_out_dir, write_xml_element_to_file};
+use crate::utils::{initialize_out_dir, write_entity_to_file, Entity};
 
 pub fn xml_explode_custom_menu_catalog<R: Read + BufRead>(
     reader: &mut Reader<R>,
     _: &BytesStart,
     out_dir_path: &Path,
     fm_file_name: &str,
-) {
+) -> References {
     let out_dir_path = out_dir_path.join("custom_menus").join(fm_file_name);
     initialize_out_dir(&out_dir_path);
 
+    let mut references = References::default();
+
     let mut depth = 1;
     let mut buf = Vec::new();
     loop {
@@ -28,7 +31,17 @@ pub fn xml_explode_custom_menu_catalog<R: Read + BufRead>(
                 depth += 1;
                 if depth == 2 {
                     if e.name().as_ref() == b"CustomMenu" {
-                        write_xml_element_to_file(reader, &e, &out_dir_path, 4);
+                        let mut custom_menu = Entity::default();
+                        custom_menu.read_xml_element(reader, &e);
+                        references.collect(
+                            "Custom menu",
+                            &custom_menu.id,
+                            &custom_menu.name,
+                            &[],
+                            None,
+                            &custom_menu.content,
+                        );
+                        write_entity_to_file(&out_dir_path, &custom_menu, 4);
                         depth -= 1;
                         continue;
                     } else {
@@ -49,4 +62,6 @@ pub fn xml_explode_custom_menu_catalog<R: Read + BufRead>(
 
         buf.clear()
     }
+
+    references
 }
@@ -0,0 +1,57 @@
+use crate::references::References;
+use crate::table_occurrence_catalog::TableOccurrence;
+use crate::utils::initialize_out_dir;
+use crate::Settings;
+use std::collections::HashMap;
+use std::path::Path;
+
+pub(crate) mod relationships;
+pub(crate) mod script_calls;
+
+pub fn write_graphs(
+    out_dir_path: &Path,
+    fm_file_name: &str,
+    references: &References,
+    table_occurrences: &[TableOccurrence],
+    script_id_path_map: &HashMap<String, Vec<String>>,
+    settings: &Settings,
+) {
+    if !settings.script_call_graph && !settings.relationship_graph {
+        return;
+    }
+
+    let out_dir_path = out_dir_path.join("graphs").join(fm_file_name);
+    initialize_out_dir(&out_dir_path);
+
+    if settings.script_call_graph {
+        script_calls::write_script_call_graph(
+            &out_dir_path,
+            &references.scripts,
+            &references.script_calls,
+            script_id_path_map,
+        );
+    }
+    if settings.relationship_graph {
+        relationships::write_relationship_graph(
+            &out_dir_path,
+            table_occurrences,
+            &references.relationships,
+        );
+    }
+}
+
+/// Escapes a label for a quoted Graphviz string.
+fn dot_label(label: &str) -> String {
+    single_line(label)
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+}
+
+/// Escapes a label for a quoted Mermaid string.
+fn mermaid_label(label: &str) -> String {
+    single_line(label).replace('"', "#quot;")
+}
+
+fn single_line(label: &str) -> String {
+    label.split(['\r', '\n']).collect::<Vec<_>>().join(" ")
+}
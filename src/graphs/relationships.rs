@@ -0,0 +1,268 @@
+use crate::graphs::{dot_label, mermaid_label};
+use crate::relationship_catalog::Relationship;
+use crate::table_occurrence_catalog::TableOccurrence;
+use crate::utils::write_text_file;
+use std::path::Path;
+
+const PALETTE: [&str; 12] = [
+    "#8dd3c7", "#ffffb3", "#bebada", "#fb8072", "#80b1d3", "#fdb462", "#b3de69", "#fccde5",
+    "#d9d9d9", "#bc80bd", "#ccebc5", "#ffed6f",
+];
+
+#[derive(Debug, Default)]
+struct Graph {
+    /// Base table ids in order of appearance, their index picks the colour.
+    base_tables: Vec<String>,
+    /// Node, label lines and index of the base table.
+    nodes: Vec<(String, Vec<String>, Option<usize>)>,
+    /// From, to and label lines.
+    edges: Vec<(String, String, Vec<String>)>,
+}
+
+pub fn write_relationship_graph(
+    out_dir_path: &Path,
+    table_occurrences: &[TableOccurrence],
+    relationships: &[Relationship],
+) {
+    let graph = build_graph(table_occurrences, relationships);
+    write_text_file(&out_dir_path.join("relationships.dot"), &to_dot(&graph));
+    write_text_file(&out_dir_path.join("relationships.mmd"), &to_mermaid(&graph));
+}
+
+fn build_graph(table_occurrences: &[TableOccurrence], relationships: &[Relationship]) -> Graph {
+    let mut graph = Graph::default();
+
+    for table_occurrence in table_occurrences {
+        let mut label = vec![table_occurrence.name.to_string()];
+        if table_occurrence.is_external {
+            label.push(format!("(external: {})", table_occurrence.base_table_name));
+        } else if !table_occurrence.base_table_name.is_empty()
+            && table_occurrence.base_table_name != table_occurrence.name
+        {
+            label.push(format!("({})", table_occurrence.base_table_name));
+        }
+
+        // base table ids of external table occurrences belong to another file
+        let base_table = match table_occurrence.base_table_id.as_str() {
+            _ if table_occurrence.is_external => None,
+            "" => None,
+            id => Some(
+                match graph
+                    .base_tables
+                    .iter()
+                    .position(|base_table| base_table == id)
+                {
+                    Some(index) => index,
+                    None => {
+                        graph.base_tables.push(id.to_string());
+                        graph.base_tables.len() - 1
+                    }
+                },
+            ),
+        };
+        graph
+            .nodes
+            .push((node_id(&table_occurrence.id), label, base_table));
+    }
+
+    for relationship in relationships {
+        let mut label = relationship
+            .predicates
+            .iter()
+            .map(|predicate| {
+                format!(
+                    "{} {} {}",
+                    predicate.left_field,
+                    predicate.operator_symbol(),
+                    predicate.right_field
+                )
+            })
+            .collect::<Vec<_>>();
+        if relationship.cascade_create() != "none" {
+            label.push(format!("create: {}", relationship.cascade_create()));
+        }
+        if relationship.cascade_delete() != "none" {
+            label.push(format!("delete: {}", relationship.cascade_delete()));
+        }
+
+        graph.edges.push((
+            node_id(&relationship.left.table_occurrence_id),
+            node_id(&relationship.right.table_occurrence_id),
+            label,
+        ));
+    }
+
+    graph
+}
+
+fn node_id(table_occurrence_id: &str) -> String {
+    format!("to_{}", table_occurrence_id)
+}
+
+fn to_dot(graph: &Graph) -> String {
+    let dot_lines = |lines: &[String]| {
+        lines
+            .iter()
+            .map(|line| dot_label(line))
+            .collect::<Vec<_>>()
+            .join("\\n")
+    };
+
+    let mut lines = vec![
+        "graph relationships {".to_string(),
+        "\trankdir=LR;".to_string(),
+        "\tnode [shape=box, style=filled, fillcolor=white];".to_string(),
+    ];
+    for (node, label, base_table) in &graph.nodes {
+        lines.push(format!(
+            "\t\"{}\" [label=\"{}\"{}];",
+            node,
+            dot_lines(label),
+            match base_table {
+                Some(index) => format!(", fillcolor=\"{}\"", PALETTE[index % PALETTE.len()]),
+                None => String::new(),
+            }
+        ));
+    }
+    for (from, to, label) in &graph.edges {
+        lines.push(format!(
+            "\t\"{}\" -- \"{}\" [label=\"{}\"];",
+            from,
+            to,
+            dot_lines(label)
+        ));
+    }
+    lines.push("}".to_string());
+
+    lines.join("\n")
+}
+
+fn to_mermaid(graph: &Graph) -> String {
+    let mermaid_lines = |lines: &[String]| {
+        lines
+            .iter()
+            .map(|line| mermaid_label(line))
+            .collect::<Vec<_>>()
+            .join("<br>")
+    };
+
+    let mut lines = vec!["flowchart LR".to_string()];
+    for (node, label, _) in &graph.nodes {
+        lines.push(format!("\t{}[\"{}\"]", node, mermaid_lines(label)));
+    }
+    for (from, to, label) in &graph.edges {
+        lines.push(format!(
+            "\t{} ---|\"{}\"| {}",
+            from,
+            mermaid_lines(label),
+            to
+        ));
+    }
+    for (index, base_table) in graph.base_tables.iter().enumerate() {
+        lines.push(format!(
+            "\tclassDef base_table_{} fill:{}",
+            base_table,
+            PALETTE[index % PALETTE.len()]
+        ));
+        lines.push(format!(
+            "\tclass {} base_table_{}",
+            graph
+                .nodes
+                .iter()
+                .filter(|(_, _, node_base_table)| *node_base_table == Some(index))
+                .map(|(node, _, _)| node.as_str())
+                .collect::<Vec<_>>()
+                .join(","),
+            base_table
+        ));
+    }
+
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::relationship_catalog::{JoinPredicate, RelationshipTable};
+
+    fn render(to_text: fn(&Graph) -> String) -> String {
+        let table_occurrence = |id: &str, name: &str, base_table_id: &str| TableOccurrence {
+            id: id.to_string(),
+            name: name.to_string(),
+            base_table_id: base_table_id.to_string(),
+            base_table_name: "Customers".to_string(),
+            is_external: false,
+        };
+        let table_occurrences = vec![
+            table_occurrence("1", "Customers", "130"),
+            table_occurrence("2", "Invoices_Customers", "130"),
+            table_occurrence("3", "Archive", ""),
+            TableOccurrence {
+                is_external: true,
+                ..table_occurrence("4", "Remote_Customers", "130")
+            },
+        ];
+        let relationships = vec![Relationship {
+            id: "7".to_string(),
+            left: RelationshipTable {
+                table_occurrence_id: "1".to_string(),
+                table_occurrence_name: "Customers".to_string(),
+                cascade_create: false,
+                cascade_delete: true,
+                sort: vec![],
+            },
+            right: RelationshipTable {
+                table_occurrence_id: "2".to_string(),
+                table_occurrence_name: "Invoices_Customers".to_string(),
+                cascade_create: true,
+                cascade_delete: true,
+                sort: vec![],
+            },
+            predicates: vec![
+                JoinPredicate {
+                    operator: "Equal".to_string(),
+                    left_field: "ID".to_string(),
+                    right_field: "ParentID".to_string(),
+                },
+                JoinPredicate {
+                    operator: "GreaterOrEqual".to_string(),
+                    left_field: "Date".to_string(),
+                    right_field: "Date \"from\"".to_string(),
+                },
+            ],
+        }];
+
+        to_text(&build_graph(&table_occurrences, &relationships))
+    }
+
+    #[test]
+    fn test_dot() {
+        assert_eq!(
+            render(to_dot),
+            "graph relationships {
+\trankdir=LR;
+\tnode [shape=box, style=filled, fillcolor=white];
+\t\"to_1\" [label=\"Customers\", fillcolor=\"#8dd3c7\"];
+\t\"to_2\" [label=\"Invoices_Customers\\n(Customers)\", fillcolor=\"#8dd3c7\"];
+\t\"to_3\" [label=\"Archive\\n(Customers)\"];
+\t\"to_4\" [label=\"Remote_Customers\\n(external: Customers)\"];
+\t\"to_1\" -- \"to_2\" [label=\"ID = ParentID\\nDate ≥ Date \\\"from\\\"\\ncreate: right\\ndelete: both\"];
+}"
+        );
+    }
+
+    #[test]
+    fn test_mermaid() {
+        assert_eq!(
+            render(to_mermaid),
+            "flowchart LR
+\tto_1[\"Customers\"]
+\tto_2[\"Invoices_Customers<br>(Customers)\"]
+\tto_3[\"Archive<br>(Customers)\"]
+\tto_4[\"Remote_Customers<br>(external: Customers)\"]
+\tto_1 ---|\"ID = ParentID<br>Date ≥ Date #quot;from#quot;<br>create: right<br>delete: both\"| to_2
+\tclassDef base_table_130 fill:#8dd3c7
+\tclass to_1,to_2 base_table_130"
+        );
+    }
+}
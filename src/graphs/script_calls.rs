@@ -0,0 +1,300 @@
+use crate::graphs::{dot_label, mermaid_label};
+use crate::join_scope_id_and_name;
+use crate::utils::write_text_file;
+use crate::where_used::scripts::ScriptCall;
+use std::collections::HashMap;
+use std::path::Path;
+
+#[derive(Debug, Default)]
+struct Folder<'a> {
+    name: &'a str,
+    scripts: Vec<(&'a str, &'a str)>,
+    folders: Vec<Folder<'a>>,
+}
+
+impl<'a> Folder<'a> {
+    fn insert(&mut self, path: &'a [String], script: (&'a str, &'a str)) {
+        let Some((name, path)) = path.split_first() else {
+            self.scripts.push(script);
+            return;
+        };
+
+        let index = match self.folders.iter().position(|folder| folder.name == name) {
+            Some(index) => index,
+            None => {
+                self.folders.push(Folder {
+                    name,
+                    ..Default::default()
+                });
+                self.folders.len() - 1
+            }
+        };
+        self.folders[index].insert(path, script);
+    }
+}
+
+#[derive(Debug, Default)]
+struct Graph<'a> {
+    root: Folder<'a>,
+    /// Nodes for scripts of other files and scripts called by name.
+    unknown_nodes: Vec<(String, String)>,
+    /// From, to, label and whether the callee is only known at runtime.
+    edges: Vec<(String, String, String, bool)>,
+}
+
+pub fn write_script_call_graph(
+    out_dir_path: &Path,
+    scripts: &[(String, String)],
+    script_calls: &[ScriptCall],
+    script_id_path_map: &HashMap<String, Vec<String>>,
+) {
+    let graph = build_graph(scripts, script_calls, script_id_path_map);
+    write_text_file(&out_dir_path.join("script_calls.dot"), &to_dot(&graph));
+    write_text_file(&out_dir_path.join("script_calls.mmd"), &to_mermaid(&graph));
+}
+
+fn build_graph<'a>(
+    scripts: &'a [(String, String)],
+    script_calls: &[ScriptCall],
+    script_id_path_map: &'a HashMap<String, Vec<String>>,
+) -> Graph<'a> {
+    let mut graph = Graph::default();
+    let script_names: HashMap<&str, &str> = scripts
+        .iter()
+        .map(|(id, name)| (id.as_str(), name.as_str()))
+        .collect();
+
+    for (id, name) in scripts {
+        let path = script_id_path_map
+            .get(id)
+            .map(|path| path.as_slice())
+            .unwrap_or_default();
+        graph.root.insert(path, (id, name));
+    }
+
+    for (index, script_call) in script_calls.iter().enumerate() {
+        let Some(step) = script_call.step else {
+            continue;
+        };
+        if script_call.caller_type != "Script" || script_call.script_name.is_empty() {
+            continue;
+        }
+
+        let id = script_call.script_id.as_str();
+        let (to, unresolved) = match script_names.get(id) {
+            Some(&name) if name == script_call.script_name => (node_id(id), false),
+            _ if id.is_empty() => {
+                let to = format!("unresolved_{}", index);
+                let label = format!("{} (unresolved)", script_call.script_name);
+                graph.unknown_nodes.push((to.to_string(), label));
+                (to, true)
+            }
+            _ => {
+                let to = format!("external_{}", index);
+                let label = format!("{} (other file)", script_call.script_name);
+                graph.unknown_nodes.push((to.to_string(), label));
+                (to, false)
+            }
+        };
+        graph.edges.push((
+            node_id(&script_call.caller_id),
+            to,
+            format!("{}: {}", step, script_call.step_name),
+            unresolved,
+        ));
+    }
+
+    graph
+}
+
+fn node_id(script_id: &str) -> String {
+    format!("script_{}", script_id)
+}
+
+fn to_dot(graph: &Graph) -> String {
+    let mut lines = vec![
+        "digraph script_calls {".to_string(),
+        "\trankdir=LR;".to_string(),
+        "\tnode [shape=box];".to_string(),
+    ];
+    dot_folder(&graph.root, 1, &mut 0, &mut lines);
+    for (node, label) in &graph.unknown_nodes {
+        lines.push(format!(
+            "\t\"{}\" [label=\"{}\", style=dashed];",
+            node,
+            dot_label(label)
+        ));
+    }
+    for (from, to, label, unresolved) in &graph.edges {
+        lines.push(format!(
+            "\t\"{}\" -> \"{}\" [label=\"{}\"{}];",
+            from,
+            to,
+            dot_label(label),
+            if *unresolved { ", style=dashed" } else { "" }
+        ));
+    }
+    lines.push("}".to_string());
+
+    lines.join("\n")
+}
+
+fn dot_folder(folder: &Folder, indent: usize, cluster: &mut usize, lines: &mut Vec<String>) {
+    let tabs = "\t".repeat(indent);
+    for (id, name) in &folder.scripts {
+        lines.push(format!(
+            "{}\"{}\" [label=\"{}\"];",
+            tabs,
+            node_id(id),
+            dot_label(&join_scope_id_and_name(id, name))
+        ));
+    }
+    for subfolder in &folder.folders {
+        lines.push(format!("{}subgraph \"cluster_{}\" {{", tabs, cluster));
+        lines.push(format!(
+            "{}\tlabel=\"{}\";",
+            tabs,
+            dot_label(subfolder.name)
+        ));
+        *cluster += 1;
+        dot_folder(subfolder, indent + 1, cluster, lines);
+        lines.push(format!("{}}}", tabs));
+    }
+}
+
+fn to_mermaid(graph: &Graph) -> String {
+    let mut lines = vec!["flowchart LR".to_string()];
+    mermaid_folder(&graph.root, 1, &mut 0, &mut lines);
+    for (node, label) in &graph.unknown_nodes {
+        lines.push(format!("\t{}[\"{}\"]", node, mermaid_label(label)));
+    }
+    for (from, to, label, unresolved) in &graph.edges {
+        lines.push(format!(
+            "\t{} {}|\"{}\"| {}",
+            from,
+            if *unresolved { "-.->" } else { "-->" },
+            mermaid_label(label),
+            to
+        ));
+    }
+    if !graph.unknown_nodes.is_empty() {
+        lines.push("\tclassDef unknown stroke-dasharray: 5 5".to_string());
+        lines.push(format!(
+            "\tclass {} unknown",
+            graph
+                .unknown_nodes
+                .iter()
+                .map(|(node, _)| node.as_str())
+                .collect::<Vec<_>>()
+                .join(",")
+        ));
+    }
+
+    lines.join("\n")
+}
+
+fn mermaid_folder(folder: &Folder, indent: usize, subgraph: &mut usize, lines: &mut Vec<String>) {
+    let tabs = "\t".repeat(indent);
+    for (id, name) in &folder.scripts {
+        lines.push(format!(
+            "{}{}[\"{}\"]",
+            tabs,
+            node_id(id),
+            mermaid_label(&join_scope_id_and_name(id, name))
+        ));
+    }
+    for subfolder in &folder.folders {
+        lines.push(format!(
+            "{}subgraph folder_{} [\"{}\"]",
+            tabs,
+            subgraph,
+            mermaid_label(subfolder.name)
+        ));
+        *subgraph += 1;
+        mermaid_folder(subfolder, indent + 1, subgraph, lines);
+        lines.push(format!("{}end", tabs));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn render(to_text: fn(&Graph) -> String) -> String {
+        let scripts = vec![
+            ("1".to_string(), "Start".to_string()),
+            ("2".to_string(), "Sub".to_string()),
+        ];
+        let script_calls = vec![
+            ScriptCall {
+                script_id: "2".to_string(),
+                script_name: "Sub".to_string(),
+                caller_type: "Script".to_string(),
+                caller_id: "1".to_string(),
+                step: Some(2),
+                step_name: "Perform Script".to_string(),
+                location: "Script: Start - ID 1 ; Step: 2".to_string(),
+            },
+            ScriptCall {
+                script_id: "2".to_string(),
+                script_name: "Sub".to_string(),
+                caller_type: "Layout".to_string(),
+                caller_id: "7".to_string(),
+                step: None,
+                step_name: String::new(),
+                location: "Layout: Home - ID 7 ; Button".to_string(),
+            },
+            ScriptCall {
+                script_id: String::new(),
+                script_name: "$script".to_string(),
+                caller_type: "Script".to_string(),
+                caller_id: "2".to_string(),
+                step: Some(5),
+                step_name: "Perform Script on Server".to_string(),
+                location: "Script: Utils - ID 3/Sub - ID 2 ; Step: 5".to_string(),
+            },
+        ];
+        let script_id_path_map = HashMap::from([
+            ("1".to_string(), vec![]),
+            ("2".to_string(), vec!["Utils - ID 3".to_string()]),
+        ]);
+
+        to_text(&build_graph(&scripts, &script_calls, &script_id_path_map))
+    }
+
+    #[test]
+    fn test_dot() {
+        assert_eq!(
+            render(to_dot),
+            "digraph script_calls {
+\trankdir=LR;
+\tnode [shape=box];
+\t\"script_1\" [label=\"Start - ID 1\"];
+\tsubgraph \"cluster_0\" {
+\t\tlabel=\"Utils - ID 3\";
+\t\t\"script_2\" [label=\"Sub - ID 2\"];
+\t}
+\t\"unresolved_2\" [label=\"$script (unresolved)\", style=dashed];
+\t\"script_1\" -> \"script_2\" [label=\"2: Perform Script\"];
+\t\"script_2\" -> \"unresolved_2\" [label=\"5: Perform Script on Server\", style=dashed];
+}"
+        );
+    }
+
+    #[test]
+    fn test_mermaid() {
+        assert_eq!(
+            render(to_mermaid),
+            "flowchart LR
+\tscript_1[\"Start - ID 1\"]
+\tsubgraph folder_0 [\"Utils - ID 3\"]
+\t\tscript_2[\"Sub - ID 2\"]
+\tend
+\tunresolved_2[\"$script (unresolved)\"]
+\tscript_1 -->|\"2: Perform Script\"| script_2
+\tscript_2 -.->|\"5: Perform Script on Server\"| unresolved_2
+\tclassDef unknown stroke-dasharray: 5 5
+\tclass unresolved_2 unknown"
+        );
+    }
+}
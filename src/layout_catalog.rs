@@ -1,3 +1,4 @@
+use crate::references::References;
 use crate::{escape_filename, join_scope_id_and_name};
 use quick_xml::events::{BytesStart, Event};
 use quick_xml::reader::Reader;
@@ -25,12 +26,14 @@ pub fn xml_explode_layout_catalog<R: Read + BufRead>(
     _: &BytesStart,
     out_dir_path: &Path,
     fm_file_name: &str,
-) {
+) -> References {
     let out_dir_path = out_dir_path.join("layouts").join(fm_file_name);
     initialize_out_dir(&out_dir_path);
 
     let mut layout_info = LayoutInfo::default();
 
+    let mut references = References::default();
+
     let mut depth = 1;
     let mut buf = Vec::new();
     loop {
@@ -102,6 +105,14 @@ pub fn xml_explode_layout_catalog<R: Read + BufRead>(
                     .push_str(end_element_to_string(&e).as_str());
 
                 if depth == 1 && local_name_to_string(e.name().as_ref()) == "Layout" {
+                    references.collect(
+                        "Layout",
+                        &layout_info.id,
+                        &layout_info.name,
+                        &layout_info.path,
+                        None,
+                        &layout_info.content,
+                    );
                     write_layout_to_file(&out_dir_path, &layout_info)
                 }
             }
@@ -125,6 +136,8 @@ pub fn xml_explode_layout_catalog<R: Read + BufRead>(
 
         buf.clear()
     }
+
+    references
 }
 
 fn write_layout_to_file(dir_path: &Path, layout: &LayoutInfo) {
@@ -9,31 +9,42 @@ use std::path::Path;
 use std::{fs, fs::File, io::BufReader, path::PathBuf, time::Instant};
 
 use crate::base_table_catalog::parse_base_table_catalog;
+use crate::broken_references::write_broken_references;
+use crate::calculations::base_table_resolver::BaseTableResolver;
+use crate::calculations::function_names::FunctionLanguage;
 use crate::custom_function_catalog::xml_explode_custom_function_catalog;
 use crate::custom_menu_catalog::xml_explode_custom_menu_catalog;
 use crate::custom_menu_set_catalog::xml_explode_custom_menu_set_catalog;
 use crate::extended_privileges_catalog::xml_explode_extended_privileges_catalog;
 use crate::external_data_source_catalog::xml_extract_external_data_sources;
+use crate::graphs::write_graphs;
 use crate::layout_catalog::xml_explode_layout_catalog;
 use crate::privilege_sets_catalog::xml_explode_privilege_set_catalog;
+use crate::references::References;
 use crate::relationship_catalog::xml_explode_relationship_catalog;
 use crate::script_catalog::parse_script_directories;
+use crate::script_steps::constants::StepLanguage;
 use crate::script_steps_catalog::xml_explode_script_catalog;
 use crate::table_catalog::xml_explode_table_catalog;
-use crate::table_occurrence_catalog::xml_explode_table_occurrence_catalog;
+use crate::table_occurrence_catalog::{xml_explode_table_occurrence_catalog, TableOccurrence};
 use crate::theme_catalog::xml_explode_theme_catalog;
 use crate::utils::attributes::get_attribute;
 use crate::value_list_catalog::xml_explode_value_list_catalog;
+use crate::where_used::fields::write_field_index;
+use crate::where_used::scripts::{write_script_index, ScriptCall};
 
 mod base_table_catalog;
+mod broken_references;
 mod calculations;
 mod custom_function_catalog;
 mod custom_menu_catalog;
 mod custom_menu_set_catalog;
 mod extended_privileges_catalog;
 mod external_data_source_catalog;
+mod graphs;
 mod layout_catalog;
 mod privilege_sets_catalog;
+mod references;
 mod relationship_catalog;
 mod script_catalog;
 mod script_steps;
@@ -43,6 +54,7 @@ mod table_occurrence_catalog;
 mod theme_catalog;
 mod utils;
 mod value_list_catalog;
+mod where_used;
 
 /// Parse all as XML exported FileMaker solutions from source directory and explode them to target directory.
 #[derive(Parser)]
@@ -53,6 +65,75 @@ struct Args {
 
     /// The target directory to write output
     target: PathBuf,
+
+    /// Show password calculations of account script steps instead of masking them
+    #[arg(long)]
+    show_passwords: bool,
+
+    /// Language of the script step names in scripts_sanitized, the labels inside the steps are kept
+    #[arg(long, value_enum, default_value_t = StepLanguage::Source)]
+    step_language: StepLanguage,
+
+    /// Language of the function names in calculations, `en` translates German function names
+    #[arg(long, value_enum, default_value_t = FunctionLanguage::Source)]
+    function_language: FunctionLanguage,
+
+    /// Pretty print calculations in scripts_sanitized, tables_sanitized and custom_functions
+    #[arg(long)]
+    pretty_print_calculations: bool,
+
+    /// Annotate field references in scripts_sanitized with their base table
+    #[arg(long)]
+    annotate_base_tables: bool,
+
+    /// Exit with an error if broken references were found
+    #[arg(long)]
+    fail_on_broken: bool,
+
+    /// Write the broken references of each file to reports
+    #[arg(long)]
+    report_broken_references: bool,
+
+    /// Write where_used/fields, the uses of each field
+    #[arg(long)]
+    where_used_fields: bool,
+
+    /// Write where_used/scripts, the callers and calls of each script
+    #[arg(long)]
+    where_used_scripts: bool,
+
+    /// Write graphs/script_calls.dot and .mmd, the calls between scripts
+    #[arg(long)]
+    script_call_graph: bool,
+
+    /// Write graphs/relationships.dot and .mmd, the relationship graph
+    #[arg(long)]
+    relationship_graph: bool,
+
+    /// Write relationships_sanitized, the relationships as text
+    #[arg(long)]
+    sanitize_relationships: bool,
+
+    /// Write tables_sanitized, the fields of each table as text
+    #[arg(long)]
+    sanitize_tables: bool,
+}
+
+#[derive(Debug, Default)]
+pub struct Settings {
+    pub show_passwords: bool,
+    pub step_language: StepLanguage,
+    pub function_language: FunctionLanguage,
+    pub pretty_print_calculations: bool,
+    pub annotate_base_tables: bool,
+    pub fail_on_broken: bool,
+    pub report_broken_references: bool,
+    pub where_used_fields: bool,
+    pub where_used_scripts: bool,
+    pub script_call_graph: bool,
+    pub relationship_graph: bool,
+    pub sanitize_relationships: bool,
+    pub sanitize_tables: bool,
 }
 
 fn main() -> Result<()> {
@@ -61,6 +142,21 @@ fn main() -> Result<()> {
     let args = Args::parse();
     let in_dir = args.source;
     let out_dir = args.target;
+    let settings = Settings {
+        show_passwords: args.show_passwords,
+        step_language: args.step_language,
+        function_language: args.function_language,
+        pretty_print_calculations: args.pretty_print_calculations,
+        annotate_base_tables: args.annotate_base_tables,
+        fail_on_broken: args.fail_on_broken,
+        report_broken_references: args.report_broken_references,
+        where_used_fields: args.where_used_fields,
+        where_used_scripts: args.where_used_scripts,
+        script_call_graph: args.script_call_graph,
+        relationship_graph: args.relationship_graph,
+        sanitize_relationships: args.sanitize_relationships,
+        sanitize_tables: args.sanitize_tables,
+    };
 
     valid_dir_or_throw(&in_dir)?;
     valid_dir_or_throw(&out_dir)?;
@@ -74,15 +170,17 @@ fn main() -> Result<()> {
     println!("Start processing {} files...", paths.len());
 
     // Process XML files in parallel
-    paths.par_iter().for_each(|path| {
-        match explode_xml(path, &out_dir) {
-            Ok(_) => {}
+    let broken_reference_count: usize = paths
+        .par_iter()
+        .map(|path| match explode_xml(path, &out_dir, &settings) {
+            Ok(broken_reference_count) => broken_reference_count,
             Err(err) => {
                 let file_name = path.file_name().unwrap().to_str().unwrap();
-                eprintln!("Failed to process file '{}': {}", file_name, err)
+                eprintln!("Failed to process file '{}': {}", file_name, err);
+                0
             }
-        };
-    });
+        })
+        .sum();
 
     let duration = start.elapsed();
     if duration.as_secs() > 9 {
@@ -91,6 +189,10 @@ fn main() -> Result<()> {
         println!("Completed in {:?} ms.", duration.as_millis());
     }
 
+    if settings.fail_on_broken && broken_reference_count > 0 {
+        bail!("Found {} broken references", broken_reference_count);
+    }
+
     Ok(())
 }
 
@@ -104,7 +206,11 @@ fn valid_dir_or_throw(dir_path: &PathBuf) -> Result<(), Error> {
     }
 }
 
-fn explode_xml(fm_export_file_path: &PathBuf, out_dir_path: &Path) -> Result<(), Error> {
+fn explode_xml(
+    fm_export_file_path: &PathBuf,
+    out_dir_path: &Path,
+    settings: &Settings,
+) -> Result<usize, Error> {
     let start = Instant::now();
     let fm_export_file_name = fm_export_file_path.file_name().unwrap().to_str().unwrap();
 
@@ -117,6 +223,9 @@ fn explode_xml(fm_export_file_path: &PathBuf, out_dir_path: &Path) -> Result<(),
     let mut depth = 0;
     let mut script_id_path_map: HashMap<String, Vec<String>> = HashMap::new();
     let mut table_name_id_map: HashMap<String, String> = HashMap::new();
+    let mut table_occurrences: Vec<TableOccurrence> = Vec::new();
+    let mut references = References::default();
+    let mut file_trigger: Option<String> = None;
 
     // Iterate over XML events
     let decode_reader = BufReader::new(DecodeReaderBytes::new(file));
@@ -130,6 +239,27 @@ fn explode_xml(fm_export_file_path: &PathBuf, out_dir_path: &Path) -> Result<(),
             }
             Ok(Event::Eof) => break,
             Ok(Event::Start(e)) => {
+                // script triggers of the file itself, e.g. OnFirstWindowOpen
+                match e.name().as_ref() {
+                    b"ScriptTrigger" => file_trigger = get_attribute(&e, "action"),
+                    b"ScriptReference" if file_trigger.is_some() => {
+                        references.script_calls.push(ScriptCall {
+                            script_id: get_attribute(&e, "id").unwrap_or_default(),
+                            script_name: get_attribute(&e, "name").unwrap_or_default(),
+                            caller_type: "File".to_string(),
+                            caller_id: String::new(),
+                            step: None,
+                            step_name: String::new(),
+                            location: format!(
+                                "File: {} ; Trigger: {}",
+                                fm_file_name,
+                                file_trigger.as_deref().unwrap_or_default()
+                            ),
+                        })
+                    }
+                    _ => {}
+                }
+
                 match depth {
                     0 => match e.name().as_ref() {
                         b"FMDynamicTemplate" | b"FMSaveAsXML" => {
@@ -154,41 +284,45 @@ fn explode_xml(fm_export_file_path: &PathBuf, out_dir_path: &Path) -> Result<(),
                             continue;
                         }
                         b"LayoutCatalog" => {
-                            xml_explode_layout_catalog(
+                            references.extend(xml_explode_layout_catalog(
                                 &mut reader,
                                 &e,
                                 out_dir_path,
                                 &fm_file_name,
-                            );
+                            ));
                             continue;
                         }
                         b"FieldsForTables" => {
-                            xml_explode_table_catalog(
+                            references.extend(xml_explode_table_catalog(
                                 &mut reader,
                                 &e,
                                 out_dir_path,
                                 &fm_file_name,
                                 &table_name_id_map,
-                            );
+                                settings,
+                            ));
                             continue;
                         }
                         b"CalcsForCustomFunctions" => {
-                            xml_explode_custom_function_catalog(
+                            references.extend(xml_explode_custom_function_catalog(
                                 &mut reader,
                                 &e,
                                 out_dir_path,
                                 &fm_file_name,
-                            );
+                                settings,
+                            ));
                             continue;
                         }
                         b"StepsForScripts" => {
-                            xml_explode_script_catalog(
+                            references.extend(xml_explode_script_catalog(
                                 &mut reader,
                                 &e,
                                 out_dir_path,
                                 &fm_file_name,
                                 &script_id_path_map,
-                            );
+                                &BaseTableResolver::new(&table_occurrences),
+                                settings,
+                            ));
                             continue;
                         }
                         b"ScriptCatalog" => {
@@ -205,25 +339,26 @@ fn explode_xml(fm_export_file_path: &PathBuf, out_dir_path: &Path) -> Result<(),
                             continue;
                         }
                         b"ValueListCatalog" => {
-                            xml_explode_value_list_catalog(
+                            references.extend(xml_explode_value_list_catalog(
                                 &mut reader,
                                 &e,
                                 out_dir_path,
                                 &fm_file_name,
-                            );
+                            ));
                             continue;
                         }
                         b"RelationshipCatalog" => {
-                            xml_explode_relationship_catalog(
+                            references.extend(xml_explode_relationship_catalog(
                                 &mut reader,
                                 &e,
                                 out_dir_path,
                                 &fm_file_name,
-                            );
+                                settings,
+                            ));
                             continue;
                         }
                         b"TableOccurrenceCatalog" => {
-                            xml_explode_table_occurrence_catalog(
+                            table_occurrences = xml_explode_table_occurrence_catalog(
                                 &mut reader,
                                 &e,
                                 out_dir_path,
@@ -263,12 +398,12 @@ fn explode_xml(fm_export_file_path: &PathBuf, out_dir_path: &Path) -> Result<(),
                             continue;
                         }
                         b"CustomMenuCatalog" => {
-                            xml_explode_custom_menu_catalog(
+                            references.extend(xml_explode_custom_menu_catalog(
                                 &mut reader,
                                 &e,
                                 out_dir_path,
                                 &fm_file_name,
-                            );
+                            ));
                             continue;
                         }
                         _ => {}
@@ -277,7 +412,10 @@ fn explode_xml(fm_export_file_path: &PathBuf, out_dir_path: &Path) -> Result<(),
                 }
                 depth += 1;
             }
-            Ok(Event::End(_)) => {
+            Ok(Event::End(e)) => {
+                if e.name().as_ref() == b"ScriptTrigger" {
+                    file_trigger = None;
+                }
                 depth -= 1;
             }
             _ => {}
@@ -285,13 +423,42 @@ fn explode_xml(fm_export_file_path: &PathBuf, out_dir_path: &Path) -> Result<(),
         buf.clear()
     }
 
+    if settings.report_broken_references {
+        write_broken_references(out_dir_path, &fm_file_name, &references.broken);
+    }
+    if settings.where_used_fields {
+        write_field_index(
+            out_dir_path,
+            &fm_file_name,
+            &references.fields,
+            &BaseTableResolver::new(&table_occurrences),
+        );
+    }
+    if settings.where_used_scripts {
+        write_script_index(
+            out_dir_path,
+            &fm_file_name,
+            &references.scripts,
+            &references.script_calls,
+            &script_id_path_map,
+        );
+    }
+    write_graphs(
+        out_dir_path,
+        &fm_file_name,
+        &references,
+        &table_occurrences,
+        &script_id_path_map,
+        settings,
+    );
+
     println!(
         "{} finished in {} ms.",
         fm_export_file_name,
         start.elapsed().as_millis()
     );
 
-    Ok(())
+    Ok(references.broken.len())
 }
 
 fn join_scope_id_and_name(scope_id: &str, scope_name: &str) -> String {
@@ -367,7 +534,7 @@ mod tests {
             .collect::<Vec<_>>();
 
         for path in paths {
-            explode_xml(&path, output_dir)
+            explode_xml(&path, output_dir, &Settings::default())
                 .unwrap_or_else(|_| panic!("Error processing file '{}'", path.display()));
         }
 
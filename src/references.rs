@@ -0,0 +1,117 @@
+use crate::broken_references::{find_broken_references, BrokenReference};
+use crate::calculations::field_reference::FieldReference;
+use crate::join_scope_id_and_name;
+use crate::relationship_catalog::Relationship;
+use crate::where_used::fields::{find_field_references, FieldUse};
+use crate::where_used::scripts::{find_script_references, ScriptCall};
+
+/// References found while exploding the objects of a file.
+#[derive(Debug, Default)]
+pub struct References {
+    pub broken: Vec<BrokenReference>,
+    pub fields: Vec<FieldUse>,
+    pub scripts: Vec<(String, String)>,
+    pub script_calls: Vec<ScriptCall>,
+    pub relationships: Vec<Relationship>,
+}
+
+impl References {
+    pub fn collect(
+        &mut self,
+        object_type: &str,
+        id: &str,
+        name: &str,
+        path: &[String],
+        step: Option<(usize, &str)>,
+        xml: &str,
+    ) {
+        let (step, step_name) = match step {
+            Some((number, step_name)) => (Some(number), step_name),
+            None => (None, ""),
+        };
+        let object = join_scope_id_and_name(id, name);
+        for (reference_type, reference) in find_broken_references(xml) {
+            self.add_broken(object_type, &object, path, step, reference_type, reference);
+        }
+
+        let location = describe_location(object_type, &object, path, step);
+        for (field_reference, field) in find_field_references(xml) {
+            let location = match field {
+                Some(field) => format!("{} ; Field: {}", location, field),
+                None => location.to_string(),
+            };
+            self.add_field(field_reference, location);
+        }
+
+        for (script_id, script_name, context) in find_script_references(xml) {
+            self.script_calls.push(ScriptCall {
+                script_id,
+                script_name,
+                caller_type: object_type.to_string(),
+                caller_id: id.to_string(),
+                step,
+                step_name: step_name.to_string(),
+                location: match context {
+                    Some(context) => format!("{} ; {}", location, context),
+                    None => location.to_string(),
+                },
+            });
+        }
+    }
+
+    pub fn add_broken(
+        &mut self,
+        object_type: &str,
+        object: &str,
+        path: &[String],
+        step: Option<usize>,
+        reference_type: String,
+        reference: String,
+    ) {
+        self.broken.push(BrokenReference {
+            object_type: object_type.to_string(),
+            object: object.to_string(),
+            path: path.to_vec(),
+            step,
+            reference_type,
+            reference,
+        });
+    }
+
+    pub fn add_field(&mut self, field_reference: FieldReference, location: String) {
+        if field_reference.id == "0" || field_reference.table_occurrence_id.is_empty() {
+            return;
+        }
+
+        self.fields.push(FieldUse {
+            field_reference,
+            location,
+        });
+    }
+
+    pub fn extend(&mut self, references: References) {
+        self.broken.extend(references.broken);
+        self.fields.extend(references.fields);
+        self.scripts.extend(references.scripts);
+        self.script_calls.extend(references.script_calls);
+        self.relationships.extend(references.relationships);
+    }
+}
+
+/// Describes where an object lives, e.g. `Script: Folder - ID 1/Name - ID 2 ; Step: 3`.
+pub fn describe_location(
+    object_type: &str,
+    object: &str,
+    path: &[String],
+    step: Option<usize>,
+) -> String {
+    let mut object_path = path.to_vec();
+    object_path.push(object.to_string());
+
+    let mut location = format!("{}: {}", object_type, object_path.join("/"));
+    if let Some(step) = step {
+        location.push_str(&format!(" ; Step: {}", step));
+    }
+
+    location
+}
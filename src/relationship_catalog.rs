@@ -1,4 +1,5 @@
-use crate::{escape_filename, join_scope_id_and_name};
+use crate::references::References;
+use crate::{escape_filename, join_scope_id_and_name, Settings};
 use quick_xml::events::{BytesStart, Event};
 use quick_xml::reader::Reader;
 use std::io::{BufRead, Read};
@@ -9,7 +10,210 @@ use crate::utils::xml_utils::{
     cdata_element_to_string, end_element_to_string, local_name_to_string, skip_element,
     start_element_to_string, text_element_to_string,
 };
-use crate::utils::{initialize_out_dir, write_xml_file};
+use crate::utils::{initialize_out_dir, write_text_file, write_xml_file};
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct RelationshipTable {
+    pub table_occurrence_id: String,
+    pub table_occurrence_name: String,
+    pub cascade_create: bool,
+    pub cascade_delete: bool,
+    /// Sort fields and their order.
+    pub sort: Vec<(String, String)>,
+}
+
+impl RelationshipTable {
+    fn display_sort(&self) -> String {
+        self.sort
+            .iter()
+            .map(|(field, order)| format!("{}::{} ({})", self.table_occurrence_name, field, order))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct JoinPredicate {
+    pub operator: String,
+    pub left_field: String,
+    pub right_field: String,
+}
+
+impl JoinPredicate {
+    pub fn operator_symbol(&self) -> &str {
+        match self.operator.as_str() {
+            "Equal" => "=",
+            "NotEqual" => "≠",
+            "Less" => "<",
+            "LessOrEqual" => "≤",
+            "Greater" => ">",
+            "GreaterOrEqual" => "≥",
+            "CartesianProduct" => "×",
+            operator => operator,
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Relationship {
+    pub id: String,
+    pub left: RelationshipTable,
+    pub right: RelationshipTable,
+    pub predicates: Vec<JoinPredicate>,
+}
+
+impl Relationship {
+    pub fn from_xml(xml: &str) -> Self {
+        let mut relationship = Relationship::default();
+        let mut in_left = true;
+        let mut in_predicate = false;
+        let mut in_sort = false;
+
+        let mut reader = Reader::from_str(xml);
+        let mut buf = Vec::new();
+        loop {
+            match reader.read_event_into(&mut buf) {
+                Err(_) | Ok(Event::Eof) => break,
+                Ok(Event::Start(e)) | Ok(Event::Empty(e)) => match e.name().as_ref() {
+                    b"Relationship" => {
+                        relationship.id = get_attribute(&e, "id").unwrap_or_default()
+                    }
+                    b"LeftTable" | b"RightTable" => {
+                        in_left = e.name().as_ref() == b"LeftTable";
+                        let table = match in_left {
+                            true => &mut relationship.left,
+                            false => &mut relationship.right,
+                        };
+                        table.cascade_create =
+                            get_attribute(&e, "cascadeCreate").unwrap_or_default() == "True";
+                        table.cascade_delete =
+                            get_attribute(&e, "cascadeDelete").unwrap_or_default() == "True";
+                    }
+                    b"TableOccurrenceReference" if !in_predicate => {
+                        let table = match in_left {
+                            true => &mut relationship.left,
+                            false => &mut relationship.right,
+                        };
+                        if table.table_occurrence_id.is_empty() {
+                            table.table_occurrence_id = get_attribute(&e, "id").unwrap_or_default();
+                            table.table_occurrence_name =
+                                get_attribute(&e, "name").unwrap_or_default();
+                        }
+                    }
+                    b"JoinPredicate" => {
+                        in_predicate = true;
+                        relationship.predicates.push(JoinPredicate {
+                            operator: get_attribute(&e, "type").unwrap_or_default(),
+                            ..Default::default()
+                        });
+                    }
+                    b"LeftField" => in_left = true,
+                    b"RightField" => in_left = false,
+                    b"SortSpecification" => {
+                        in_sort = get_attribute(&e, "value").unwrap_or_default() == "True"
+                    }
+                    b"Sort" if in_sort => {
+                        let table = match in_left {
+                            true => &mut relationship.left,
+                            false => &mut relationship.right,
+                        };
+                        table
+                            .sort
+                            .push((String::new(), get_attribute(&e, "type").unwrap_or_default()));
+                    }
+                    b"FieldReference" if in_sort => {
+                        let table = match in_left {
+                            true => &mut relationship.left,
+                            false => &mut relationship.right,
+                        };
+                        if let Some(sort) = table.sort.last_mut() {
+                            if sort.0.is_empty() {
+                                sort.0 = get_attribute(&e, "name").unwrap_or_default();
+                            }
+                        }
+                    }
+                    b"FieldReference" if in_predicate => {
+                        if let Some(predicate) = relationship.predicates.last_mut() {
+                            let name = get_attribute(&e, "name").unwrap_or_default();
+                            match in_left {
+                                true => predicate.left_field = name,
+                                false => predicate.right_field = name,
+                            }
+                        }
+                    }
+                    _ => {}
+                },
+                Ok(Event::End(e)) => match e.name().as_ref() {
+                    b"JoinPredicate" => in_predicate = false,
+                    b"SortSpecification" => in_sort = false,
+                    _ => {}
+                },
+                _ => {}
+            }
+            buf.clear()
+        }
+
+        relationship
+    }
+
+    /// Renders the relationship as one line, e.g.
+    /// `Invoices::CustomerID = Customers::ID (allow create: right, delete: none, sort: none)`.
+    pub fn display(&self) -> String {
+        let predicates = self
+            .predicates
+            .iter()
+            .map(|predicate| {
+                format!(
+                    "{}::{} {} {}::{}",
+                    self.left.table_occurrence_name,
+                    predicate.left_field,
+                    predicate.operator_symbol(),
+                    self.right.table_occurrence_name,
+                    predicate.right_field
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(" AND ");
+
+        let sort = match (self.left.sort.is_empty(), self.right.sort.is_empty()) {
+            (true, true) => "none".to_string(),
+            (false, true) => format!("left by {}", self.left.display_sort()),
+            (true, false) => format!("right by {}", self.right.display_sort()),
+            (false, false) => format!(
+                "left by {}, right by {}",
+                self.left.display_sort(),
+                self.right.display_sort()
+            ),
+        };
+
+        format!(
+            "{} (allow create: {}, delete: {}, sort: {})",
+            predicates,
+            self.cascade_create(),
+            self.cascade_delete(),
+            sort
+        )
+    }
+
+    /// Returns the sides records can be created on through this relationship.
+    pub fn cascade_create(&self) -> &'static str {
+        sides(self.left.cascade_create, self.right.cascade_create)
+    }
+
+    /// Returns the sides records are deleted on when a related record is deleted.
+    pub fn cascade_delete(&self) -> &'static str {
+        sides(self.left.cascade_delete, self.right.cascade_delete)
+    }
+}
+
+fn sides(left: bool, right: bool) -> &'static str {
+    match (left, right) {
+        (true, true) => "both",
+        (true, false) => "left",
+        (false, true) => "right",
+        (false, false) => "none",
+    }
+}
 
 #[derive(Debug, Default)]
 struct RelationshipInfo {
@@ -24,14 +228,23 @@ pub fn xml_explode_relationship_catalog<R: Read + BufRead>(
     _: &BytesStart,
     out_dir_path: &Path,
     fm_file_name: &str,
-) {
-    let out_dir_path = out_dir_path.join("relationships").join(fm_file_name);
-    initialize_out_dir(&out_dir_path);
+    settings: &Settings,
+) -> References {
+    let xml_out_dir_path = out_dir_path.join("relationships").join(fm_file_name);
+    let text_out_dir_path = out_dir_path
+        .join("relationships_sanitized")
+        .join(fm_file_name);
+    initialize_out_dir(&xml_out_dir_path);
+    if settings.sanitize_relationships {
+        initialize_out_dir(&text_out_dir_path);
+    }
 
     let mut relationship_info = RelationshipInfo::default();
     let mut in_left = false;
     let mut in_right = false;
 
+    let mut references = References::default();
+
     let mut depth = 1;
     let mut buf = Vec::new();
     loop {
@@ -94,7 +307,27 @@ pub fn xml_explode_relationship_catalog<R: Read + BufRead>(
                     .push_str(end_element_to_string(&e).as_str());
 
                 if depth == 1 && local_name_to_string(e.name().as_ref()) == "Relationship" {
-                    write_relationship_to_file(&out_dir_path, &relationship_info);
+                    references.collect(
+                        "Relationship",
+                        &relationship_info.id,
+                        &format!(
+                            "[{}] - [{}]",
+                            relationship_info.left, relationship_info.right
+                        ),
+                        &[],
+                        None,
+                        &relationship_info.content,
+                    );
+                    let relationship = Relationship::from_xml(&relationship_info.content);
+                    write_relationship_to_file(&xml_out_dir_path, &relationship_info);
+                    if settings.sanitize_relationships {
+                        write_relationship_to_text_file(
+                            &text_out_dir_path,
+                            &relationship_info,
+                            &relationship.display(),
+                        );
+                    }
+                    references.relationships.push(relationship);
                     relationship_info.id.clear();
                     relationship_info.left.clear();
                     relationship_info.right.clear();
@@ -130,14 +363,104 @@ pub fn xml_explode_relationship_catalog<R: Read + BufRead>(
 
         buf.clear()
     }
+
+    references
 }
 
 fn write_relationship_to_file(output_dir: &Path, relationship: &RelationshipInfo) {
+    let output_file_path = output_dir.join(format!("{}.xml", relationship_filename(relationship)));
+    write_xml_file(&output_file_path, &relationship.content, 4);
+}
+
+fn write_relationship_to_text_file(
+    output_dir: &Path,
+    relationship: &RelationshipInfo,
+    content: &str,
+) {
+    let output_file_path = output_dir.join(format!("{}.txt", relationship_filename(relationship)));
+    write_text_file(&output_file_path, content);
+}
+
+fn relationship_filename(relationship: &RelationshipInfo) -> String {
     let relationship_filename = join_scope_id_and_name(
         relationship.id.as_str(),
         format!("[{}] - [{}]", relationship.left, relationship.right).as_str(),
     );
-    let relationship_filename = escape_filename(&relationship_filename).replace('.', "_");
-    let output_file_path = output_dir.join(format!("{}.xml", relationship_filename));
-    write_xml_file(&output_file_path, &relationship.content, 4);
+    escape_filename(&relationship_filename).replace('.', "_")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_relationship_from_xml() {
+        let xml_input = "
+        <Relationship id=\"39\">
+            <LeftTable cascadeCreate=\"False\" cascadeDelete=\"False\" type=\"Local\">
+                <TableOccurrenceReference id=\"1065091\" name=\"Invoices\"></TableOccurrenceReference>
+                <SortSpecification value=\"False\" maintain=\"True\"></SortSpecification>
+            </LeftTable>
+            <RightTable cascadeCreate=\"True\" cascadeDelete=\"False\" type=\"Local\">
+                <TableOccurrenceReference id=\"1065089\" name=\"Customers\"></TableOccurrenceReference>
+                <SortSpecification value=\"True\" maintain=\"True\">
+                    <SortList membercount=\"1\">
+                        <Sort type=\"Descending\">
+                            <PrimaryField>
+                                <FieldReference id=\"3\" name=\"Name\">
+                                    <TableOccurrenceReference id=\"1065089\" name=\"Customers\"></TableOccurrenceReference>
+                                </FieldReference>
+                            </PrimaryField>
+                        </Sort>
+                    </SortList>
+                </SortSpecification>
+            </RightTable>
+            <JoinPredicateList membercount=\"1\">
+                <JoinPredicate type=\"Equal\">
+                    <LeftField>
+                        <FieldReference id=\"2\" name=\"CustomerID\">
+                            <TableOccurrenceReference id=\"1065091\" name=\"Invoices\"></TableOccurrenceReference>
+                        </FieldReference>
+                    </LeftField>
+                    <RightField>
+                        <FieldReference id=\"1\" name=\"ID\">
+                            <TableOccurrenceReference id=\"1065089\" name=\"Customers\"></TableOccurrenceReference>
+                        </FieldReference>
+                    </RightField>
+                </JoinPredicate>
+            </JoinPredicateList>
+        </Relationship>
+        ";
+
+        let relationship = Relationship::from_xml(xml_input);
+        assert_eq!(
+            relationship.display(),
+            "Invoices::CustomerID = Customers::ID (allow create: right, delete: none, sort: right by Customers::Name (Descending))"
+        );
+        assert_eq!(
+            relationship,
+            Relationship {
+                id: "39".to_string(),
+                left: RelationshipTable {
+                    table_occurrence_id: "1065091".to_string(),
+                    table_occurrence_name: "Invoices".to_string(),
+                    cascade_create: false,
+                    cascade_delete: false,
+                    sort: vec![],
+                },
+                right: RelationshipTable {
+                    table_occurrence_id: "1065089".to_string(),
+                    table_occurrence_name: "Customers".to_string(),
+                    cascade_create: true,
+                    cascade_delete: false,
+                    sort: vec![("Name".to_string(), "Descending".to_string())],
+                },
+                predicates: vec![JoinPredicate {
+                    operator: "Equal".to_string(),
+                    left_field: "CustomerID".to_string(),
+                    right_field: "ID".to_string(),
+                }],
+            }
+        );
+    }
 }
@@ -0,0 +1,259 @@
+use crate::calculations::calculation::Calculation;
+use crate::utils::attributes::get_attribute;
+use html_escape::decode_html_entities;
+use quick_xml::events::Event;
+use quick_xml::Reader;
+
+const MASKED_PASSWORD: &str = "********";
+
+pub fn sanitize(step: &str, show_passwords: bool) -> Option<String> {
+    let mut id = String::new();
+    let mut name = String::new();
+    let mut params: Vec<String> = Vec::new();
+    let mut calculations: Vec<String> = Vec::new();
+    let mut privilege_set = String::new();
+
+    let mut reader = Reader::from_str(step);
+    reader.trim_text(true);
+    let mut buf: Vec<u8> = Vec::new();
+    loop {
+        match reader.read_event_into(&mut buf) {
+            Err(_) => continue,
+            Ok(Event::Eof) => break,
+            Ok(Event::Start(e)) => match e.name().as_ref() {
+                b"Step" => {
+                    id = get_attribute(&e, "id").unwrap_or_default();
+                    name = get_attribute(&e, "name").unwrap().to_string();
+                }
+                b"Boolean" if get_attribute(&e, "type").unwrap_or_default() == "enable" => {
+                    // the name is the selected action, the value only tells which one it is
+                    params.push(get_attribute(&e, "name").unwrap_or_default());
+                }
+                b"Boolean" => {
+                    let label = match get_attribute(&e, "type").unwrap_or_default().as_str() {
+                        "Password" => "Expire password".to_string(),
+                        label => label.to_string(),
+                    };
+                    params.push(format!(
+                        "{}: {}",
+                        label,
+                        match get_attribute(&e, "value").unwrap_or_default().as_str() {
+                            "True" => "ON",
+                            _ => "OFF",
+                        }
+                    ));
+                }
+                b"PrivilegeSetReference" => {
+                    privilege_set =
+                        decode_html_entities(&get_attribute(&e, "name").unwrap_or_default())
+                            .to_string();
+                }
+                b"Parameter" => match get_attribute(&e, "type").unwrap_or_default().as_str() {
+                    "Boolean" | "PrivilegeSetReference" => {}
+                    parameter_type => {
+                        let mut calculation = Calculation::from_xml(&mut reader, &e).unwrap();
+                        if calculation.is_empty() {
+                            continue;
+                        }
+                        if !show_passwords && is_password(&id, parameter_type) {
+                            calculation = MASKED_PASSWORD.to_string();
+                        }
+                        calculations.push(format!("{}: {}", label(parameter_type), calculation));
+                    }
+                },
+                _ => {}
+            },
+            _ => {}
+        }
+        buf.clear()
+    }
+
+    params.splice(0..0, calculations);
+    if !privilege_set.is_empty() {
+        params.push(format!("Privilege set: {}", privilege_set));
+    }
+
+    if name.is_empty() {
+        None
+    } else if params.is_empty() {
+        Some(format!("{} []", name))
+    } else {
+        Some(format!("{} [ {} ]", name, params.join(" ; ")))
+    }
+}
+
+/// Add Account and Reset Account Password pass the password as `Password`, Change Password
+/// passes the old and the new password as `Old` and `New`.
+fn is_password(step_id: &str, parameter_type: &str) -> bool {
+    matches!(
+        (step_id, parameter_type),
+        (_, "Password") | ("83", "Old") | ("83", "New")
+    )
+}
+
+fn label(parameter_type: &str) -> &str {
+    match parameter_type {
+        "Name" | "AccountName" | "Calculation" => "Account name",
+        "Old" => "Old password",
+        "New" => "New password",
+        parameter_type => parameter_type,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ADD_ACCOUNT: &str = "
+        <Step id=\"134\" name=\"Konto hinzufügen\" enable=\"True\">
+            <ParameterValues membercount=\"4\">
+                <Parameter type=\"AccountName\">
+                    <Calculation datatype=\"1\" position=\"0\">
+                        <Calculation>
+                            <Text><![CDATA[$Account]]></Text>
+                            <ChunkList hash=\"5D3C3A7E6F2B41E7A0B1C2D3E4F50617\">
+                                <Chunk type=\"VariableReference\">$Account</Chunk>
+                            </ChunkList>
+                        </Calculation>
+                    </Calculation>
+                </Parameter>
+                <Parameter type=\"Password\">
+                    <Calculation datatype=\"1\" position=\"1\">
+                        <Calculation>
+                            <Text><![CDATA[\"secret\"]]></Text>
+                            <ChunkList hash=\"6E4D4B8F7A3C52F8B1C2D3E4F5061728\">
+                                <Chunk type=\"NoRef\">&quot;secret&quot;</Chunk>
+                            </ChunkList>
+                        </Calculation>
+                    </Calculation>
+                </Parameter>
+                <Parameter type=\"PrivilegeSetReference\">
+                    <PrivilegeSetReference id=\"2\" name=\"&lt;Data Entry Only&gt;\"></PrivilegeSetReference>
+                </Parameter>
+                <Parameter type=\"Boolean\">
+                    <Boolean type=\"Passwort ändern\" value=\"True\"></Boolean>
+                </Parameter>
+            </ParameterValues>
+        </Step>
+        ";
+
+    #[test]
+    fn test_sanitize_add_account_masks_password() {
+        let expected_output = Some("Konto hinzufügen [ Account name: $Account ; Password: ******** ; Passwort ändern: ON ; Privilege set: <Data Entry Only> ]".to_string());
+        assert_eq!(sanitize(ADD_ACCOUNT.trim(), false), expected_output);
+    }
+
+    #[test]
+    fn test_sanitize_add_account_shows_password() {
+        let expected_output = Some("Konto hinzufügen [ Account name: $Account ; Password: \"secret\" ; Passwort ändern: ON ; Privilege set: <Data Entry Only> ]".to_string());
+        assert_eq!(sanitize(ADD_ACCOUNT.trim(), true), expected_output);
+    }
+
+    #[test]
+    fn test_sanitize_enable_account() {
+        let xml_input = "
+        <Step id=\"137\" name=\"Konto aktivieren\" enable=\"True\">
+            <ParameterValues membercount=\"1\">
+                <Parameter type=\"Boolean\">
+                    <Boolean value=\"True\" name=\"Aktivieren\" type=\"enable\"></Boolean>
+                </Parameter>
+            </ParameterValues>
+        </Step>
+        ";
+
+        let expected_output = Some("Konto aktivieren [ Aktivieren ]".to_string());
+        assert_eq!(sanitize(xml_input.trim(), false), expected_output);
+    }
+
+    #[test]
+    fn test_sanitize_disable_account() {
+        let xml_input = "
+        <Step id=\"137\" name=\"Konto aktivieren\" enable=\"True\">
+            <ParameterValues membercount=\"1\">
+                <Parameter type=\"Boolean\">
+                    <Boolean value=\"False\" name=\"Deaktivieren\" type=\"enable\"></Boolean>
+                </Parameter>
+            </ParameterValues>
+        </Step>
+        ";
+
+        let expected_output = Some("Konto aktivieren [ Deaktivieren ]".to_string());
+        assert_eq!(sanitize(xml_input.trim(), false), expected_output);
+    }
+
+    #[test]
+    fn test_sanitize_reset_account_password() {
+        let xml_input = "
+        <Step id=\"136\" name=\"Kontopasswort zurücksetzen\" enable=\"True\">
+            <ParameterValues membercount=\"1\">
+                <Parameter type=\"Boolean\">
+                    <Boolean value=\"False\" type=\"Password\"></Boolean>
+                </Parameter>
+            </ParameterValues>
+        </Step>
+        ";
+
+        let expected_output =
+            Some("Kontopasswort zurücksetzen [ Expire password: OFF ]".to_string());
+        assert_eq!(sanitize(xml_input.trim(), false), expected_output);
+    }
+
+    const CHANGE_PASSWORD: &str = "
+        <Step id=\"83\" name=\"Passwort ändern\" enable=\"True\">
+            <ParameterValues membercount=\"3\">
+                <Parameter type=\"Old\">
+                    <Calculation datatype=\"1\" position=\"0\">
+                        <Calculation>
+                            <Text><![CDATA[$PasswortAlt]]></Text>
+                        </Calculation>
+                    </Calculation>
+                </Parameter>
+                <Parameter type=\"New\">
+                    <Calculation datatype=\"1\" position=\"1\">
+                        <Calculation>
+                            <Text><![CDATA[$PasswortNeu]]></Text>
+                        </Calculation>
+                    </Calculation>
+                </Parameter>
+                <Parameter type=\"Boolean\">
+                    <Boolean type=\"Mit Dialog\" id=\"128\" value=\"False\"></Boolean>
+                </Parameter>
+            </ParameterValues>
+        </Step>
+        ";
+
+    #[test]
+    fn test_sanitize_change_password_masks_passwords() {
+        let expected_output = Some(
+            "Passwort ändern [ Old password: ******** ; New password: ******** ; Mit Dialog: OFF ]"
+                .to_string(),
+        );
+        assert_eq!(sanitize(CHANGE_PASSWORD.trim(), false), expected_output);
+    }
+
+    #[test]
+    fn test_sanitize_change_password_shows_passwords() {
+        let expected_output = Some("Passwort ändern [ Old password: $PasswortAlt ; New password: $PasswortNeu ; Mit Dialog: OFF ]".to_string());
+        assert_eq!(sanitize(CHANGE_PASSWORD.trim(), true), expected_output);
+    }
+
+    #[test]
+    fn test_sanitize_delete_account() {
+        let xml_input = "
+        <Step id=\"135\" name=\"Konto löschen\" enable=\"True\">
+            <ParameterValues membercount=\"1\">
+                <Parameter type=\"Calculation\">
+                    <Calculation datatype=\"1\" position=\"0\">
+                        <Calculation>
+                            <Text><![CDATA[$Account]]></Text>
+                        </Calculation>
+                    </Calculation>
+                </Parameter>
+            </ParameterValues>
+        </Step>
+        ";
+
+        let expected_output = Some("Konto löschen [ Account name: $Account ]".to_string());
+        assert_eq!(sanitize(xml_input.trim(), false), expected_output);
+    }
+}
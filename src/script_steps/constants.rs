@@ -1,3 +1,4 @@
+use clap::ValueEnum;
 use strum_macros::FromRepr;
 
 const UNKNOWN_SCRIPT_STEP: [u32; 16] = [
@@ -16,6 +17,10 @@ pub enum ScriptStep {
     DuplicateRecordRequest = 8,
     DeleteRecordRequest = 9,
     DeleteAllRecords = 10,
+    InsertFromIndex = 11,
+    InsertFromLastVisited = 12,
+    InsertCurrentDate = 13,
+    InsertCurrentTime = 14,
     GoToRecordRequestPage = 16,
     GoToField = 17,
     CheckRecord = 19,
@@ -28,29 +33,52 @@ pub enum ScriptStep {
     OmitMultipleRecords = 26,
     ShowOmittedOnly = 27,
     PerformFind = 28,
+    ShowHideToolbars = 29,
+    ViewAs = 30,
+    AdjustWindow = 31,
     OpenHelp = 32,
+    ImportRecords = 35,
+    ExportRecords = 36,
     OpenManageDatabase = 38,
+    SortRecords = 39,
     ExitApplication = 44,
     SelectAll = 50,
     EnterBrowseMode = 55,
+    InsertPicture = 56,
+    SendEvent = 57,
+    InsertCurrentUserName = 60,
+    InsertText = 61,
+    PauseResumeScript = 62,
+    SendMail = 63,
+    SendDdeExecute = 64,
+    DialPhone = 65,
+    PerformAppleScript = 67,
     IfStart = 68,
     Else = 69,
     IfEnd = 70,
     LoopStart = 71,
     ExitLoopIf = 72,
     LoopEnd = 73,
+    GoToRelatedRecord = 74,
     CommitRecordRequests = 75,
     SetFieldData = 76,
+    InsertCalculatedResult = 77,
     FixWindow = 79,
+    RefreshWindow = 80,
+    ScrollWindow = 81,
     NewFile = 82,
+    ChangePassword = 83,
     AllowUserAbort = 85,
     SetErrorRecording = 86,
+    ShowCustomDialog = 87,
     OpenScriptWorkspace = 88,
     Comment = 89,
     HaltScript = 90,
     ReplaceFieldContents = 91,
+    ShowHideTextRuler = 92,
     Beep = 93,
     SetUseSystemFormats = 94,
+    SetZoomLevel = 97,
     GoToPortalRow = 99,
     CopyRecordRequest = 101,
     FlushCacheToDisk = 102,
@@ -60,28 +88,88 @@ pub enum ScriptStep {
     SpellingOptions = 107,
     SelectDictionaries = 108,
     EditUserDictionary = 109,
+    OpenUrl = 111,
     OpenManageValueLists = 112,
     OpenSharing = 113,
     OpenFileOptions = 114,
     AllowFormattingBar = 115,
+    ExecuteSql = 117,
     OpenHosts = 118,
+    MoveResizeWindow = 119,
+    ArrangeAllWindows = 120,
     CloseWindow = 121,
     NewWindow = 122,
+    SelectWindow = 123,
+    SetWindowTitle = 124,
     IfElse = 125,
     ConstrainFoundSet = 126,
     ExtendFoundSet = 127,
     OpenFindReplace = 129,
+    InsertFile = 131,
+    AddAccount = 134,
+    DeleteAccount = 135,
+    ResetAccountPassword = 136,
+    EnableAccount = 137,
+    ReLogin = 138,
     OpenManageDataSources = 140,
     SetVariable = 141,
+    SaveRecordsAsExcel = 143,
+    SaveRecordsAsPdf = 144,
     GoToObject = 145,
+    SetFieldByName = 147,
     OpenEditSavedFinds = 149,
     OpenManageLayouts = 151,
+    SortRecordsByField = 154,
+    FindMatchingRecords = 155,
     OpenManageContainers = 156,
+    InsertPdf = 158,
+    InsertAudioVideo = 159,
+    InsertFromUrl = 160,
+    InsertFromDevice = 161,
+    PerformScriptOnServer = 164,
     OpenManageThemes = 165,
+    ShowHideMenubar = 166,
     RefreshObject = 167,
     ClosePopover = 169,
     UploadToServer = 172,
+    EnableTouchKeyboard = 174,
+    PerformJavaScriptInWebViewer = 175,
+    SetAllowedOrientations = 176,
+    AvPlayerPlay = 177,
+    AvPlayerSetPlaybackState = 178,
+    AvPlayerSetOptions = 179,
+    RefreshPortal = 180,
+    GetFolderPath = 181,
+    TruncateTable = 182,
     OpenMyApps = 183,
+    ConfigureRegionMonitorScript = 185,
+    ConfigureLocalNotification = 187,
+    GetFileExists = 188,
+    GetFileSize = 189,
+    CreateDataFile = 190,
+    OpenDataFile = 191,
+    WriteToDataFile = 192,
+    ReadFromDataFile = 193,
+    GetDataFilePosition = 194,
+    SetDataFilePosition = 195,
+    CloseDataFile = 196,
+    DeleteFile = 197,
+    RenameFile = 199,
+    SetErrorLogging = 200,
+    ConfigureNfcReading = 201,
+    ConfigureMachineLearningModel = 202,
+    ExecuteFileMakerDataApi = 203,
+    OpenTransaction = 205,
+    CommitTransaction = 206,
+    RevertTransaction = 207,
+    SetSessionIdentifier = 208,
+    SetDictionary = 209,
+    PerformScriptOnServerWithCallback = 210,
+    TriggerClarisConnectFlow = 211,
+    ConfigureAiAccount = 212,
+    InsertEmbedding = 213,
+    InsertEmbeddingInFoundSet = 214,
+    PerformSemanticFind = 215,
 }
 
 pub fn id_to_script_step(id: &str) -> ScriptStep {
@@ -92,3 +180,336 @@ pub fn id_to_script_step(id: &str) -> ScriptStep {
         ScriptStep::from_repr(id).unwrap_or(ScriptStep::Unknown)
     }
 }
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, ValueEnum)]
+pub enum StepLanguage {
+    /// Keep the step names of the exporting FileMaker client
+    #[default]
+    Source,
+    /// English step names
+    En,
+    /// German step names
+    De,
+}
+
+const STEP_NAMES: [(u32, &str, &str); 198] = [
+    (1, "Perform Script", "Script ausführen"),
+    (3, "Save a Copy as XML", "Kopie als XML speichern"),
+    (4, "Go to Next Field", "Gehe zu nächstem Feld"),
+    (5, "Go to Previous Field", "Gehe zu vorherigem Feld"),
+    (6, "Go to Layout", "Gehe zu Layout"),
+    (7, "New Record/Request", "Neuer Datensatz/Abfrage"),
+    (
+        8,
+        "Duplicate Record/Request",
+        "Datensatz/Abfrage duplizieren",
+    ),
+    (9, "Delete Record/Request", "Datensatz/Abfrage löschen"),
+    (10, "Delete All Records", "Alle Datensätze löschen"),
+    (11, "Insert from Index", "Aus Index einfügen"),
+    (
+        12,
+        "Insert from Last Visited",
+        "Aus zuletzt geöffnetem Satz einfügen",
+    ),
+    (13, "Insert Current Date", "Systemdatum einfügen"),
+    (14, "Insert Current Time", "Systemuhrzeit einfügen"),
+    (
+        16,
+        "Go to Record/Request/Page",
+        "Gehe zu Datens./Abfrage/Seite",
+    ),
+    (17, "Go to Field", "Gehe zu Feld"),
+    (18, "Check Selection", "Nur Auswahl prüfen"),
+    (19, "Check Record", "Aktuellen Datensatz prüfen"),
+    (20, "Check Found Set", "Ganze Ergebnismenge prüfen"),
+    (21, "Unsort Records", "Sortierung aufheben"),
+    (22, "Enter Find Mode", "Suchenmodus aktivieren"),
+    (23, "Show All Records", "Alle Datensätze anzeigen"),
+    (24, "Modify Last Find", "Letzte Suche ändern"),
+    (25, "Omit Record", "Aktuellen Datens. ausschließen"),
+    (26, "Omit Multiple Records", "Mehrere ausschließen"),
+    (27, "Show Omitted Only", "Nur Ausgeschlossene anzeigen"),
+    (28, "Perform Find", "Ergebnismenge suchen"),
+    (29, "Show/Hide Toolbars", "Werkzeugleisten ein-/ausblenden"),
+    (30, "View As", "Datensätze zeigen als"),
+    (31, "Adjust Window", "Fenster anpassen"),
+    (32, "Open Help", "Hilfe"),
+    (33, "Open File", "Datei öffnen"),
+    (34, "Close File", "Datei schließen"),
+    (35, "Import Records", "Datensätze importieren"),
+    (36, "Export Records", "Datensätze exportieren"),
+    (37, "Save a Copy as", "Kopie speichern unter"),
+    (38, "Open Manage Database", "Datenbank verwalten"),
+    (39, "Sort Records", "Sortieren"),
+    (40, "Relookup Field Contents", "Referenzwerte holen"),
+    (41, "Enter Preview Mode", "Seitenansichtsmodus aktivieren"),
+    (42, "Print Setup", "Drucker einrichten"),
+    (43, "Print", "Drucken"),
+    (44, "Exit Application", "Programm beenden"),
+    (45, "Undo/Redo", "Rückgängig/Wiederholen"),
+    (46, "Cut", "Ausschneiden"),
+    (47, "Copy", "Kopieren"),
+    (48, "Paste", "Einfügen"),
+    (49, "Clear", "Löschen"),
+    (50, "Select All", "Alles auswählen"),
+    (
+        51,
+        "Revert Record/Request",
+        "Verwerfe Änderung Datens./Abfrage",
+    ),
+    (55, "Enter Browse Mode", "Blätternmodus aktivieren"),
+    (56, "Insert Picture", "Bild einfügen"),
+    (57, "Send Event", "Event senden"),
+    (59, "Insert QuickTime", "QuickTime einfügen"),
+    (60, "Insert Current User Name", "Benutzernamen einfügen"),
+    (61, "Insert Text", "Text einfügen"),
+    (62, "Pause/Resume Script", "Scriptpause setzen"),
+    (63, "Send Mail", "E-Mail senden"),
+    (64, "Send DDE Execute", "DDE Execute senden"),
+    (65, "Dial Phone", "Telefonnummer wählen"),
+    (66, "Speak", "Sprechen"),
+    (67, "Perform AppleScript", "AppleScript ausführen"),
+    (68, "If", "Wenn"),
+    (69, "Else", "Sonst"),
+    (70, "End If", "Ende (wenn)"),
+    (71, "Loop", "Schleife (Anfang)"),
+    (72, "Exit Loop If", "Verlasse Schleife wenn"),
+    (73, "End Loop", "Schleife (Ende)"),
+    (74, "Go to Related Record", "Gehe zu Bezugsdatensatz"),
+    (
+        75,
+        "Commit Records/Requests",
+        "Schreibe Änderung Datens./Abfrage",
+    ),
+    (76, "Set Field", "Feldwert setzen"),
+    (77, "Insert Calculated Result", "Berechneten Wert einfügen"),
+    (78, "Insert Object", "Objekt einfügen (Windows OLE)"),
+    (79, "Freeze Window", "Fenster fixieren"),
+    (80, "Refresh Window", "Fenster aktualisieren"),
+    (81, "Scroll Window", "Fenster rollen"),
+    (82, "New File", "Neue Datei"),
+    (83, "Change Password", "Passwort ändern"),
+    (84, "Set Multi-User", "Netzwerkzugriff einstellen"),
+    (85, "Allow User Abort", "AnwenderAbbruchZulassen setzen"),
+    (86, "Set Error Capture", "Fehleraufzeichnung setzen"),
+    (87, "Show Custom Dialog", "Eigenes Dialogfeld anzeigen"),
+    (88, "Open Script Workspace", "Scriptarbeitsbereich öffnen"),
+    (89, "# (comment)", "# (Kommentar)"),
+    (90, "Halt Script", "Alle Scripts abbrechen"),
+    (91, "Replace Field Contents", "Ersetze alle Feldwerte"),
+    (92, "Show/Hide Text Ruler", "Textlineal ein-/ausblenden"),
+    (93, "Beep", "Fehlerton"),
+    (
+        94,
+        "Set Use System Formats",
+        "SystemformateVerwenden setzen",
+    ),
+    (95, "Recover File", "Datei wiederherstellen"),
+    (
+        96,
+        "Save a Copy as Add-on Package",
+        "Kopie speichern als Add-on-Paket",
+    ),
+    (97, "Set Zoom Level", "Zoomstufe setzen"),
+    (
+        98,
+        "Copy All Records/Requests",
+        "Alle Datens./Abfragen kopieren",
+    ),
+    (99, "Go to Portal Row", "Gehe zu Ausschnittreihe"),
+    (101, "Copy Record/Request", "Datensatz/Abfrage kopieren"),
+    (102, "Flush Cache to Disk", "Cache auf Platte ablegen"),
+    (103, "Exit Script", "Aktuelles Script verlassen"),
+    (104, "Delete Portal Row", "Ausschnittreihe löschen"),
+    (105, "Open Preferences", "Einstellungen"),
+    (106, "Correct Word", "Wort korrigieren"),
+    (107, "Spelling Options", "Dateioptionen - Rechtschreibung"),
+    (108, "Select Dictionaries", "Wörterbücher wählen"),
+    (109, "Edit User Dictionary", "Anwenderwörterbuch bearbeiten"),
+    (111, "Open URL", "URL öffnen"),
+    (112, "Open Manage Value Lists", "Wertelisten verwalten"),
+    (113, "Open Sharing", "Sharing - FileMaker Netzwerk"),
+    (114, "Open File Options", "Dateioptionen"),
+    (115, "Allow Formatting Bar", "Formatierungsleiste zulassen"),
+    (
+        116,
+        "Set Next Serial Value",
+        "Nächste fortlaufende Nummer setzen",
+    ),
+    (117, "Execute SQL", "SQL ausführen"),
+    (118, "Open Hosts", "Hosts öffnen"),
+    (119, "Move/Resize Window", "Fensterposition/-größe ändern"),
+    (120, "Arrange All Windows", "Alle Fenster anordnen"),
+    (121, "Close Window", "Fenster schließen"),
+    (122, "New Window", "Neues Fenster"),
+    (123, "Select Window", "Fenster aktivieren"),
+    (124, "Set Window Title", "Fenstertitel setzen"),
+    (125, "Else If", "Sonst, wenn"),
+    (126, "Constrain Found Set", "Ergebnismenge einschränken"),
+    (127, "Extend Found Set", "Ergebnismenge erweitern"),
+    (128, "Perform Find/Replace", "Suchen/Ersetzen ausführen"),
+    (129, "Open Find/Replace", "Suchen/Ersetzen"),
+    (130, "Set Selection", "Auswahl festlegen"),
+    (131, "Insert File", "Datei einfügen"),
+    (132, "Export Field Contents", "Exportiere alle Feldwerte"),
+    (133, "Open Record/Request", "Datensatz/Abfrage öffnen"),
+    (134, "Add Account", "Konto hinzufügen"),
+    (135, "Delete Account", "Konto löschen"),
+    (136, "Reset Account Password", "Kontopasswort zurücksetzen"),
+    (137, "Enable Account", "Konto aktivieren"),
+    (138, "Re-Login", "Erneut anmelden"),
+    (139, "Convert File", "Datei konvertieren"),
+    (140, "Open Manage Data Sources", "Datenquellen verwalten"),
+    (141, "Set Variable", "Variable setzen"),
+    (142, "Install Menu Set", "Menüset installieren"),
+    (
+        143,
+        "Save Records as Excel",
+        "Datensätze als Excel speichern",
+    ),
+    (144, "Save Records as PDF", "Datensätze als PDF speichern"),
+    (145, "Go to Object", "Gehe zu Objekt"),
+    (146, "Set Web Viewer", "Web Viewer festlegen"),
+    (147, "Set Field By Name", "Feld nach Namen einstellen"),
+    (
+        148,
+        "Install OnTimer Script",
+        "BeiTimer-Script installieren",
+    ),
+    (
+        149,
+        "Open Edit Saved Finds",
+        "Gespeicherte Suchen bearbeiten",
+    ),
+    (150, "Perform Quick Find", "Schnellsuche durchführen"),
+    (151, "Open Manage Layouts", "Layouts verwalten"),
+    (
+        152,
+        "Save Records as Snapshot Link",
+        "Datensätze als Snapshot-Link speichern",
+    ),
+    (
+        154,
+        "Sort Records by Field",
+        "Datensätze nach Feld sortieren",
+    ),
+    (155, "Find Matching Records", "Übereinst. Datensätze suchen"),
+    (156, "Manage Containers", "Container verwalten"),
+    (157, "Install Plug-In File", "Plugin-Datei installieren"),
+    (158, "Insert PDF", "PDF einfügen"),
+    (159, "Insert Audio/Video", "Audio/Video einfügen"),
+    (160, "Insert from URL", "Aus URL einfügen"),
+    (161, "Insert from Device", "Von Gerät einfügen"),
+    (
+        164,
+        "Perform Script on Server",
+        "Script auf Server ausführen",
+    ),
+    (165, "Open Manage Themes", "Designs verwalten"),
+    (166, "Show/Hide Menubar", "Menüleiste ein-/ausblenden"),
+    (167, "Refresh Object", "Objekt aktualisieren"),
+    (
+        168,
+        "Set Layout Object Animation",
+        "Layoutobjekt-Animation festlegen",
+    ),
+    (169, "Close Popover", "Popover schließen"),
+    (172, "Open Upload to Host", "Auf Host hochladen"),
+    (174, "Enable Touch Keyboard", "Touch-Tastatur aktivieren"),
+    (
+        175,
+        "Perform JavaScript in Web Viewer",
+        "JavaScript in Web Viewer ausführen",
+    ),
+    (
+        176,
+        "Set Allowed Orientations",
+        "Zulässige Ausrichtungen festlegen",
+    ),
+    (177, "AVPlayer Play", "AVPlayer - Wiedergabe"),
+    (
+        178,
+        "AVPlayer Set Playback State",
+        "AVPlayer-Wiedergabestatus festlegen",
+    ),
+    (179, "AVPlayer Set Options", "AVPlayer - Optionen festlegen"),
+    (180, "Refresh Portal", "Ausschnitt aktualisieren"),
+    (181, "Get Folder Path", "Ordnerpfad holen"),
+    (182, "Truncate Table", "Tabelle leeren"),
+    (183, "Open Favorites", "Favoriten öffnen"),
+    (184, "Open Starter Solution", "Starter-Lösung öffnen"),
+    (
+        185,
+        "Configure Region Monitor Script",
+        "Regionsmonitor-Script konfigurieren",
+    ),
+    (
+        187,
+        "Configure Local Notification",
+        "Lokale Benachrichtigung konfigurieren",
+    ),
+    (188, "Get File Exists", "Dateiexistenz holen"),
+    (189, "Get File Size", "Dateigröße holen"),
+    (190, "Create Data File", "Datendatei erstellen"),
+    (191, "Open Data File", "Datendatei öffnen"),
+    (192, "Write to Data File", "In Datendatei schreiben"),
+    (193, "Read from Data File", "Von Datendatei lesen"),
+    (194, "Get Data File Position", "Datendateiposition holen"),
+    (195, "Set Data File Position", "Datendateiposition setzen"),
+    (196, "Close Data File", "Datendatei schließen"),
+    (197, "Delete File", "Datei löschen"),
+    (199, "Rename File", "Datei umbenennen"),
+    (200, "Set Error Logging", "Fehlerprotokollierung setzen"),
+    (201, "Configure NFC Reading", "NFC-Erkennung konfigurieren"),
+    (
+        202,
+        "Configure Machine Learning Model",
+        "Machine Learning-Modell konfigurieren",
+    ),
+    (
+        203,
+        "Execute FileMaker Data API",
+        "FileMaker Data API ausführen",
+    ),
+    (205, "Open Transaction", "Transaktion öffnen"),
+    (206, "Commit Transaction", "Transaktion bestätigen"),
+    (207, "Revert Transaction", "Transaktion rückgängig"),
+    (208, "Set Session Identifier", "Sitzungs-ID festlegen"),
+    (209, "Set Dictionary", "Wörterbuch festlegen"),
+    (
+        210,
+        "Perform Script on Server with Callback",
+        "Script auf Server mit Callback ausführen",
+    ),
+    (
+        211,
+        "Trigger Claris Connect Flow",
+        "Claris Connect-Flow auslösen",
+    ),
+    (212, "Configure AI Account", "KI-Konto konfigurieren"),
+    (213, "Insert Embedding", "Einbettung einfügen"),
+    (
+        214,
+        "Insert Embedding in Found Set",
+        "Einbettung in Ergebnismenge einfügen",
+    ),
+    (
+        215,
+        "Perform Semantic Find",
+        "Semantische Suche durchführen",
+    ),
+];
+
+/// Name of the step in the given language, falling back to the name of the export.
+pub fn step_name<'a>(id: &str, source_name: &'a str, language: StepLanguage) -> &'a str {
+    let names = id
+        .parse::<u32>()
+        .ok()
+        .and_then(|id| STEP_NAMES.iter().find(|(step_id, _, _)| *step_id == id));
+    match (names, language) {
+        (Some((_, en, _)), StepLanguage::En) => en,
+        (Some((_, _, de)), StepLanguage::De) => de,
+        _ => source_name,
+    }
+}
@@ -0,0 +1,182 @@
+use crate::calculations::calculation::Calculation;
+use crate::calculations::field_reference::FieldReference;
+use crate::calculations::path_list::UniversalPathList;
+use crate::utils::attributes::get_attribute;
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+
+fn parameter_value(reader: &mut Reader<&[u8]>, _: &BytesStart) -> String {
+    let mut depth = 1;
+    let mut value = String::new();
+
+    let mut buf: Vec<u8> = Vec::new();
+    loop {
+        match reader.read_event_into(&mut buf) {
+            Err(_) => continue,
+            Ok(Event::Eof) => break,
+            Ok(Event::Start(e)) => match e.name().as_ref() {
+                b"Calculation" => value = Calculation::from_xml(reader, &e).unwrap(),
+                b"FieldReference" => {
+                    value = FieldReference::from_xml(reader, &e)
+                        .unwrap()
+                        .display()
+                        .unwrap()
+                }
+                b"Variable" => {
+                    depth += 1;
+                    value = get_attribute(&e, "value").unwrap_or_default();
+                }
+                _ => depth += 1,
+            },
+            Ok(Event::End(_)) => {
+                depth -= 1;
+                if depth == 0 {
+                    break;
+                }
+            }
+            _ => {}
+        }
+        buf.clear();
+    }
+
+    value
+}
+
+pub fn sanitize(step: &str) -> Option<String> {
+    let mut name = String::new();
+    let mut params: Vec<String> = Vec::new();
+    let mut path_list = UniversalPathList::default();
+    let mut values: Vec<String> = Vec::new();
+    let mut encoding = String::new();
+
+    let mut reader = Reader::from_str(step);
+    reader.trim_text(true);
+    let mut buf: Vec<u8> = Vec::new();
+    loop {
+        match reader.read_event_into(&mut buf) {
+            Err(_) => continue,
+            Ok(Event::Eof) => break,
+            Ok(Event::Start(e)) => match e.name().as_ref() {
+                b"Step" => name = get_attribute(&e, "name").unwrap().to_string(),
+                b"Boolean" => {
+                    params.push(format!(
+                        "{}: {}",
+                        get_attribute(&e, "type").unwrap_or_default(),
+                        match get_attribute(&e, "value").unwrap_or_default().as_str() {
+                            "True" => "ON",
+                            _ => "OFF",
+                        }
+                    ));
+                }
+                b"Encoding" => encoding = get_attribute(&e, "name").unwrap_or_default(),
+                b"UniversalPathList" => {
+                    path_list = UniversalPathList::from_xml(&mut reader, &e).unwrap();
+                }
+                b"Parameter" => match get_attribute(&e, "type").unwrap_or_default().as_str() {
+                    "Boolean" | "UniversalPathList" => {}
+                    parameter_type => {
+                        let value = parameter_value(&mut reader, &e);
+                        if !value.is_empty() {
+                            values.push(format!("{}: {}", parameter_type, value));
+                        }
+                    }
+                },
+                _ => {}
+            },
+            _ => {}
+        }
+        buf.clear()
+    }
+
+    if !path_list.paths.is_empty() {
+        params.push(format!("Path: {}", path_list.display().unwrap()));
+    }
+    params.extend(values);
+    if !encoding.is_empty() {
+        params.push(format!("Encoding: {}", encoding));
+    }
+
+    if name.is_empty() {
+        None
+    } else if params.is_empty() {
+        Some(format!("{} []", name))
+    } else {
+        Some(format!("{} [ {} ]", name, params.join(" ; ")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sanitize_open_data_file() {
+        let xml_input = "
+        <Step id=\"191\" name=\"Datendatei öffnen\" enable=\"True\">
+            <ParameterValues membercount=\"2\">
+                <Parameter type=\"UniversalPathList\">
+                    <UniversalPathList membercount=\"1\">
+                        <ObjectList>
+                            <Location><![CDATA[$Path]]></Location>
+                        </ObjectList>
+                    </UniversalPathList>
+                </Parameter>
+                <Parameter type=\"Target\">
+                    <Variable value=\"$FileID\">
+                        <repetition value=\"1\"></repetition>
+                    </Variable>
+                </Parameter>
+            </ParameterValues>
+        </Step>
+        ";
+
+        let expected_output =
+            Some("Datendatei öffnen [ Path: \"$Path\" ; Target: $FileID ]".to_string());
+        assert_eq!(sanitize(xml_input.trim()), expected_output);
+    }
+
+    #[test]
+    fn test_sanitize_write_to_data_file() {
+        let xml_input = "
+        <Step id=\"192\" name=\"In Datendatei schreiben\" enable=\"True\">
+            <Options>514</Options>
+            <ParameterValues membercount=\"3\">
+                <Encoding type=\"1\" name=\"UTF-16\"></Encoding>
+                <Parameter type=\"Boolean\">
+                    <Boolean type=\"Zeilenumbruch anhängen\" id=\"512\" value=\"True\"></Boolean>
+                </Parameter>
+                <Parameter type=\"FileID\">
+                    <Calculation datatype=\"1\" position=\"0\">
+                        <Calculation>
+                            <Text><![CDATA[$FileID]]></Text>
+                            <ChunkList hash=\"8A1D9C1A5B7F0C7F0E7A4B1D2C3E4F50\">
+                                <Chunk type=\"VariableReference\">$FileID</Chunk>
+                            </ChunkList>
+                        </Calculation>
+                    </Calculation>
+                </Parameter>
+                <Parameter type=\"DataSource\">
+                    <FieldReference id=\"2\" name=\"Log\">
+                        <repetition value=\"1\"></repetition>
+                        <TableOccurrenceReference id=\"1065089\" name=\"_Home\"></TableOccurrenceReference>
+                    </FieldReference>
+                </Parameter>
+            </ParameterValues>
+        </Step>
+        ";
+
+        let expected_output = Some("In Datendatei schreiben [ Zeilenumbruch anhängen: ON ; FileID: $FileID ; DataSource: _Home::Log ; Encoding: UTF-16 ]".to_string());
+        assert_eq!(sanitize(xml_input.trim()), expected_output);
+    }
+
+    #[test]
+    fn test_sanitize_close_data_file_without_parameters() {
+        let xml_input = "
+        <Step id=\"196\" name=\"Datendatei schließen\" enable=\"True\">
+        </Step>
+        ";
+
+        let expected_output = Some("Datendatei schließen []".to_string());
+        assert_eq!(sanitize(xml_input.trim()), expected_output);
+    }
+}
@@ -0,0 +1,100 @@
+use crate::calculations::calculation::Calculation;
+use crate::calculations::path_list::UniversalPathList;
+use crate::utils::attributes::get_attribute;
+use quick_xml::events::Event;
+use quick_xml::Reader;
+
+pub fn sanitize(step: &str) -> Option<String> {
+    let mut name = String::new();
+    let mut params: Vec<String> = Vec::new();
+    let mut in_sql = false;
+
+    let mut reader = Reader::from_str(step);
+    reader.trim_text(true);
+    let mut buf: Vec<u8> = Vec::new();
+    loop {
+        match reader.read_event_into(&mut buf) {
+            Err(_) => continue,
+            Ok(Event::Eof) => break,
+            Ok(Event::Start(e)) => match e.name().as_ref() {
+                b"Step" => name = get_attribute(&e, "name").unwrap().to_string(),
+                b"Boolean" => {
+                    params.push(format!(
+                        "{}: {}",
+                        get_attribute(&e, "type").unwrap_or_default(),
+                        match get_attribute(&e, "value").unwrap_or_default().as_str() {
+                            "True" => "ON",
+                            _ => "OFF",
+                        }
+                    ));
+                }
+                b"SQL" => in_sql = true,
+                b"DSN" => {
+                    let dsn = get_attribute(&e, "name").unwrap_or_default();
+                    if !dsn.is_empty() {
+                        params.push(format!("ODBC: {}", dsn));
+                    }
+                }
+                b"Calculation" if in_sql => params.push(format!(
+                    "SQL text: {}",
+                    Calculation::from_xml(&mut reader, &e).unwrap()
+                )),
+                b"UniversalPathList" if in_sql => params.push(format!(
+                    "SQL file: {}",
+                    UniversalPathList::from_xml(&mut reader, &e)
+                        .unwrap()
+                        .display()
+                        .unwrap()
+                )),
+                _ => {}
+            },
+            Ok(Event::End(e)) if e.name().as_ref() == b"SQL" => in_sql = false,
+            _ => {}
+        }
+        buf.clear()
+    }
+
+    if name.is_empty() {
+        None
+    } else {
+        Some(format!("{} [ {} ]", name, params.join(" ; ")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sanitize() {
+        let xml_input = "
+        <Step id=\"117\" name=\"SQL ausführen\" enable=\"True\">
+            <Options>160</Options>
+            <ParameterValues membercount=\"3\">
+                <Parameter type=\"Boolean\">
+                    <Boolean type=\"Mit Dialog\" id=\"128\" value=\"False\"></Boolean>
+                </Parameter>
+                <Parameter type=\"Boolean\">
+                    <Boolean type=\"Ordner erstellen\" id=\"512\" value=\"False\"></Boolean>
+                </Parameter>
+                <Parameter type=\"SQL\">
+                    <SQL suppress=\"False\" Save=\"False\" DSN=\"False\" Script=\"False\" import=\"False\">
+                        <DSN name=\"Orders\" UserName=\"admin\" Password=\"secret\"></DSN>
+                        <Calculation>
+                            <Text><![CDATA[SELECT * FROM orders]]></Text>
+                            <ChunkList hash=\"CB471852663E7DC6BF8B28F777B6ECB5\">
+                                <Chunk type=\"VariableReference\">SELECT</Chunk>
+                                <Chunk type=\"NoRef\"> * </Chunk>
+                                <Chunk type=\"VariableReference\">FROM orders</Chunk>
+                            </ChunkList>
+                        </Calculation>
+                    </SQL>
+                </Parameter>
+            </ParameterValues>
+        </Step>
+        ";
+
+        let expected_output = Some("SQL ausführen [ Mit Dialog: OFF ; Ordner erstellen: OFF ; ODBC: Orders ; SQL text: SELECT * FROM orders ]".to_string());
+        assert_eq!(sanitize(xml_input.trim()), expected_output);
+    }
+}
@@ -0,0 +1,184 @@
+use crate::calculations::field_reference::FieldReference;
+use crate::calculations::path_list::UniversalPathList;
+use crate::utils::attributes::get_attribute;
+use quick_xml::events::Event;
+use quick_xml::Reader;
+
+#[derive(Debug, Default)]
+struct XmlTrackingState {
+    in_group: bool,
+    in_field: bool,
+}
+
+pub fn sanitize(step: &str) -> Option<String> {
+    let mut name = String::new();
+    let mut params: Vec<String> = Vec::new();
+    let mut path_list = UniversalPathList::default();
+    let mut character_set = String::new();
+    let mut formatting = false;
+    let mut group_by: Vec<String> = Vec::new();
+    let mut export_order: Vec<String> = Vec::new();
+    let mut xml_pos = XmlTrackingState::default();
+
+    let mut reader = Reader::from_str(step);
+    reader.trim_text(true);
+    let mut buf: Vec<u8> = Vec::new();
+    loop {
+        match reader.read_event_into(&mut buf) {
+            Err(_) => continue,
+            Ok(Event::Eof) => break,
+            Ok(Event::Start(e)) => match e.name().as_ref() {
+                b"Step" => name = get_attribute(&e, "name").unwrap().to_string(),
+                b"Boolean" => {
+                    params.push(format!(
+                        "{}: {}",
+                        get_attribute(&e, "type").unwrap_or_default(),
+                        match get_attribute(&e, "value").unwrap_or_default().as_str() {
+                            "True" => "ON",
+                            _ => "OFF",
+                        }
+                    ));
+                }
+                b"UniversalPathList" => {
+                    path_list = UniversalPathList::from_xml(&mut reader, &e).unwrap();
+                }
+                b"Options" => {
+                    character_set = get_attribute(&e, "name").unwrap_or_default();
+                    formatting = get_attribute(&e, "Formatting").unwrap_or_default() == "True";
+                }
+                b"Group" => xml_pos.in_group = true,
+                b"Field" => xml_pos.in_field = true,
+                b"FieldReference" => {
+                    let field_reference = FieldReference::from_xml(&mut reader, &e)
+                        .unwrap()
+                        .display()
+                        .unwrap();
+                    if xml_pos.in_group {
+                        group_by.push(field_reference);
+                    } else if xml_pos.in_field {
+                        export_order.push(field_reference);
+                    }
+                }
+                _ => {}
+            },
+            Ok(Event::End(e)) => match e.name().as_ref() {
+                b"Group" => xml_pos.in_group = false,
+                b"Field" => xml_pos.in_field = false,
+                _ => {}
+            },
+            _ => {}
+        }
+        buf.clear()
+    }
+
+    if !path_list.paths.is_empty() {
+        params.push(format!("Path: {}", path_list.display().unwrap()));
+    }
+    if path_list.auto_open {
+        params.push("Automatically open".to_string());
+    }
+    if path_list.create_mail {
+        params.push("Create email".to_string());
+    }
+    if !character_set.is_empty() {
+        params.push(format!("Character set: {}", character_set));
+    }
+    if formatting {
+        params.push("Apply current layout's data formatting".to_string());
+    }
+    if !group_by.is_empty() {
+        params.push(format!("Group by: {}", group_by.join(", ")));
+    }
+    if !export_order.is_empty() {
+        params.push(format!("Export order: {}", export_order.join(", ")));
+    }
+
+    if name.is_empty() {
+        None
+    } else {
+        Some(format!("{} [ {} ]", name, params.join(" ; ")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sanitize() {
+        let xml_input = "
+        <Step id=\"36\" name=\"Datensätze exportieren\" enable=\"True\">
+            <Options>32</Options>
+            <ParameterValues membercount=\"3\">
+                <Parameter type=\"Boolean\">
+                    <Boolean type=\"Mit Dialog\" id=\"128\" value=\"True\"></Boolean>
+                </Parameter>
+                <Parameter type=\"Boolean\">
+                    <Boolean type=\"Ordner erstellen\" id=\"512\" value=\"False\"></Boolean>
+                </Parameter>
+                <Parameter type=\"UniversalPathList\">
+                    <UniversalPathList fileType=\"TABS\" membercount=\"1\">
+                        <ObjectList>
+                            <Location><![CDATA[file:file.txt]]></Location>
+                        </ObjectList>
+                    </UniversalPathList>
+                </Parameter>
+            </ParameterValues>
+        </Step>
+        ";
+
+        let expected_output = Some("Datensätze exportieren [ Mit Dialog: ON ; Ordner erstellen: OFF ; Path: \"file:file.txt\" ]".to_string());
+        assert_eq!(sanitize(xml_input.trim()), expected_output);
+    }
+
+    #[test]
+    fn test_sanitize_export_order() {
+        let xml_input = "
+        <Step id=\"36\" name=\"Datensätze exportieren\" enable=\"True\">
+            <Options>1644200096</Options>
+            <ParameterValues membercount=\"4\">
+                <Parameter type=\"Boolean\">
+                    <Boolean type=\"Mit Dialog\" id=\"128\" value=\"False\"></Boolean>
+                </Parameter>
+                <Parameter type=\"Boolean\">
+                    <Boolean type=\"Ordner erstellen\" id=\"512\" value=\"False\"></Boolean>
+                </Parameter>
+                <Parameter type=\"UniversalPathList\">
+                    <UniversalPathList fileType=\"TABS\" AutoOpen=\"True\" CreateMail=\"True\" membercount=\"1\">
+                        <ObjectList>
+                            <Location><![CDATA[file:file.txt]]></Location>
+                        </ObjectList>
+                    </UniversalPathList>
+                </Parameter>
+                <Parameter type=\"Export\">
+                    <Export>
+                        <Options name=\"Unicode (UTF-8)\" value=\"4\" Formatting=\"True\"></Options>
+                        <Order type=\"Group\">
+                            <Group value=\"1\">
+                                <FieldReference id=\"3\" name=\"ColorName\">
+                                    <TableOccurrenceReference id=\"1065113\" name=\"_Syntax\"></TableOccurrenceReference>
+                                </FieldReference>
+                            </Group>
+                        </Order>
+                        <Order type=\"Field\">
+                            <Field value=\"1\">
+                                <FieldReference id=\"1\" name=\"__ID\">
+                                    <TableOccurrenceReference id=\"1065123\" name=\"_Home\"></TableOccurrenceReference>
+                                </FieldReference>
+                            </Field>
+                            <Field value=\"2\">
+                                <FieldReference id=\"2\" name=\"Name\">
+                                    <TableOccurrenceReference id=\"1065123\" name=\"_Home\"></TableOccurrenceReference>
+                                </FieldReference>
+                            </Field>
+                        </Order>
+                    </Export>
+                </Parameter>
+            </ParameterValues>
+        </Step>
+        ";
+
+        let expected_output = Some("Datensätze exportieren [ Mit Dialog: OFF ; Ordner erstellen: OFF ; Path: \"file:file.txt\" ; Automatically open ; Create email ; Character set: Unicode (UTF-8) ; Apply current layout's data formatting ; Group by: _Syntax::ColorName ; Export order: _Home::__ID, _Home::Name ]".to_string());
+        assert_eq!(sanitize(xml_input.trim()), expected_output);
+    }
+}
@@ -0,0 +1,138 @@
+use crate::calculations::field_reference::FieldReference;
+use crate::utils::attributes::get_attribute;
+use html_escape::decode_html_entities;
+use quick_xml::events::Event;
+use quick_xml::Reader;
+
+pub fn sanitize(step: &str) -> Option<String> {
+    let mut name = String::new();
+    let mut params: Vec<String> = Vec::new();
+    let mut field_reference = String::new();
+
+    let mut reader = Reader::from_str(step);
+    reader.trim_text(true);
+    let mut buf: Vec<u8> = Vec::new();
+    loop {
+        match reader.read_event_into(&mut buf) {
+            Err(_) => continue,
+            Ok(Event::Eof) => break,
+            Ok(Event::Start(e)) => match e.name().as_ref() {
+                b"Step" => name = get_attribute(&e, "name").unwrap().to_string(),
+                b"List" => params.push(
+                    decode_html_entities(&get_attribute(&e, "name").unwrap_or_default())
+                        .to_string(),
+                ),
+                b"Boolean" => {
+                    params.push(format!(
+                        "{}: {}",
+                        get_attribute(&e, "type").unwrap_or_default().trim(),
+                        match get_attribute(&e, "value").unwrap_or_default().as_str() {
+                            "True" => "ON",
+                            _ => "OFF",
+                        }
+                    ));
+                }
+                b"FieldReference" => {
+                    field_reference = FieldReference::from_xml(&mut reader, &e)
+                        .unwrap()
+                        .display()
+                        .unwrap();
+                }
+                b"BaseTableReference" => {
+                    // Truncate Table also names the table in its list parameter
+                    let table = get_attribute(&e, "name").unwrap_or_default();
+                    if !params.contains(&table) {
+                        field_reference = table;
+                    }
+                }
+                _ => {}
+            },
+            _ => {}
+        }
+        buf.clear()
+    }
+
+    if !field_reference.is_empty() {
+        params.push(field_reference);
+    }
+
+    if name.is_empty() {
+        None
+    } else if params.is_empty() {
+        Some(format!("{} []", name))
+    } else {
+        Some(format!("{} [ {} ]", name, params.join(" ; ")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sanitize_sort_records_by_field() {
+        let xml_input = "
+        <Step id=\"154\" name=\"Datensätze nach Feld sortieren\" enable=\"True\">
+            <Options>2</Options>
+            <ParameterValues membercount=\"2\">
+                <Parameter type=\"List\">
+                    <List name=\"Absteigend\" value=\"2\"></List>
+                </Parameter>
+                <Parameter type=\"FieldReference\">
+                    <FieldReference id=\"3\" name=\"Name\">
+                        <repetition value=\"1\"></repetition>
+                        <TableOccurrenceReference id=\"1065089\" name=\"Contacts\"></TableOccurrenceReference>
+                    </FieldReference>
+                </Parameter>
+            </ParameterValues>
+        </Step>
+        ";
+
+        let expected_output =
+            Some("Datensätze nach Feld sortieren [ Absteigend ; Contacts::Name ]".to_string());
+        assert_eq!(sanitize(xml_input.trim()), expected_output);
+    }
+
+    #[test]
+    fn test_sanitize_truncate_table() {
+        let xml_input = "
+        <Step id=\"182\" name=\"Tabelle leeren\" enable=\"True\">
+            <Options>2</Options>
+            <ParameterValues membercount=\"2\">
+                <Parameter type=\"Boolean\">
+                    <Boolean type=\"Mit Dialog\" id=\"128\" value=\"True\"></Boolean>
+                </Parameter>
+                <Parameter type=\"List\">
+                    <List name=\"&lt;Aktuelle Tabelle&gt;\" value=\"0\"></List>
+                </Parameter>
+            </ParameterValues>
+        </Step>
+        ";
+
+        let expected_output =
+            Some("Tabelle leeren [ Mit Dialog: ON ; <Aktuelle Tabelle> ]".to_string());
+        assert_eq!(sanitize(xml_input.trim()), expected_output);
+    }
+
+    #[test]
+    fn test_sanitize_truncate_specified_table() {
+        let xml_input = "
+        <Step id=\"182\" name=\"Tabelle leeren\" enable=\"True\">
+            <Options>0</Options>
+            <ParameterValues membercount=\"2\">
+                <Parameter type=\"Boolean\">
+                    <Boolean type=\"Mit Dialog\" id=\"128\" value=\"False\"></Boolean>
+                </Parameter>
+                <Parameter type=\"List\">
+                    <List name=\"_GUISyntax\" value=\"134\">
+                        <BaseTableReference id=\"134\" name=\"_GUISyntax\"></BaseTableReference>
+                    </List>
+                </Parameter>
+            </ParameterValues>
+        </Step>
+        ";
+
+        let expected_output = Some("Tabelle leeren [ Mit Dialog: OFF ; _GUISyntax ]".to_string());
+        assert_eq!(sanitize(xml_input.trim()), expected_output);
+    }
+}
@@ -0,0 +1,215 @@
+use crate::calculations::layout_reference::LayoutReferenceContainer;
+use crate::script_steps::new_window;
+use crate::utils::attributes::get_attribute;
+use quick_xml::events::Event;
+use quick_xml::Reader;
+
+#[derive(Debug, Default)]
+struct XmlTrackingState {
+    in_window_reference: bool,
+    has_window_reference: bool,
+}
+
+pub fn sanitize(step: &str) -> Option<String> {
+    let mut name = String::new();
+    let mut params: Vec<String> = Vec::new();
+    let mut table_occurrence = String::new();
+    let mut layout_reference = String::new();
+    let mut animation = String::new();
+    let mut show_related = false;
+    let mut match_found_set = false;
+    let mut xml_pos = XmlTrackingState::default();
+
+    let mut reader = Reader::from_str(step);
+    reader.trim_text(true);
+    let mut buf: Vec<u8> = Vec::new();
+    loop {
+        match reader.read_event_into(&mut buf) {
+            Err(_) => continue,
+            Ok(Event::Eof) => break,
+            Ok(Event::Start(e)) => match e.name().as_ref() {
+                b"Step" => name = get_attribute(&e, "name").unwrap().to_string(),
+                b"TableOccurrenceReference" => {
+                    table_occurrence = get_attribute(&e, "name").unwrap_or_default();
+                }
+                b"LayoutReferenceContainer" if !xml_pos.in_window_reference => {
+                    let container = LayoutReferenceContainer::from_xml(&mut reader, &e).unwrap();
+                    layout_reference = match (
+                        container.reference_type.as_str(),
+                        container.layout_reference.is_empty(),
+                    ) {
+                        ("5", true) => "Layout: <Original layout>".to_string(),
+                        _ => container.display().unwrap_or_default(),
+                    };
+                }
+                b"Animation" => animation = get_attribute(&e, "name").unwrap_or_default(),
+                b"WindowReference" => {
+                    xml_pos.in_window_reference = true;
+                    xml_pos.has_window_reference = true;
+                }
+                b"Options" if !xml_pos.in_window_reference => {
+                    show_related = get_attribute(&e, "ShowRelated").unwrap_or_default() == "True";
+                    match_found_set =
+                        get_attribute(&e, "matchFoundSet").unwrap_or_default() == "True";
+                }
+                _ => {}
+            },
+            Ok(Event::End(e)) if e.name().as_ref() == b"WindowReference" => {
+                xml_pos.in_window_reference = false;
+            }
+            _ => {}
+        }
+        buf.clear()
+    }
+
+    if !table_occurrence.is_empty() {
+        params.push(format!("From table: {}", table_occurrence));
+    }
+    if !layout_reference.is_empty() {
+        params.push(layout_reference);
+    }
+    if !animation.is_empty() {
+        params.push(format!("Animation: {}", animation));
+    }
+    if show_related {
+        params.push("Show only related records".to_string());
+    }
+    if match_found_set {
+        params.push("Match all records in current found set".to_string());
+    }
+    if xml_pos.has_window_reference {
+        let (_, mut window) = new_window::parse(step);
+        window.layout_reference.clear();
+        params.push("New window".to_string());
+        params.extend(window.params());
+    }
+
+    if name.is_empty() {
+        None
+    } else if params.is_empty() {
+        Some(format!("{} []", name))
+    } else {
+        Some(format!("{} [ {} ]", name, params.join(" ; ")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sanitize_original_layout() {
+        let xml_input = "
+        <Step id=\"74\" name=\"Gehe zu Bezugsdatensatz\" enable=\"True\">
+            <ParameterValues membercount=\"1\">
+                <Parameter type=\"Related\">
+                    <LayoutReferenceContainer value=\"1\">
+                        <Label>Originallayout</Label>
+                    </LayoutReferenceContainer>
+                    <Animation name=\"Ohne\" value=\"0\"></Animation>
+                    <Options></Options>
+                </Parameter>
+            </ParameterValues>
+        </Step>
+        ";
+
+        let expected_output = Some(
+            "Gehe zu Bezugsdatensatz [ Layout: <Originallayout> ; Animation: Ohne ]".to_string(),
+        );
+        assert_eq!(sanitize(xml_input.trim()), expected_output);
+    }
+
+    #[test]
+    fn test_sanitize_original_layout_without_label() {
+        let xml_input = "
+        <Step id=\"74\" name=\"Gehe zu Bezugsdatensatz\" enable=\"True\">
+            <ParameterValues membercount=\"1\">
+                <Parameter type=\"Related\">
+                    <TableOccurrenceReference id=\"1065130\" name=\"Color.Palette_byName\"></TableOccurrenceReference>
+                    <LayoutReferenceContainer value=\"5\"></LayoutReferenceContainer>
+                    <Animation name=\"Ohne\" value=\"0\"></Animation>
+                    <Options></Options>
+                </Parameter>
+            </ParameterValues>
+        </Step>
+        ";
+
+        let expected_output = Some(
+            "Gehe zu Bezugsdatensatz [ From table: Color.Palette_byName ; Layout: <Original layout> ; Animation: Ohne ]"
+                .to_string(),
+        );
+        assert_eq!(sanitize(xml_input.trim()), expected_output);
+    }
+
+    #[test]
+    fn test_sanitize_match_found_set() {
+        let xml_input = "
+        <Step id=\"74\" name=\"Gehe zu Bezugsdatensatz\" enable=\"True\">
+            <Options>33554952</Options>
+            <ParameterValues membercount=\"1\">
+                <Parameter type=\"Related\">
+                    <TableOccurrenceReference id=\"1065130\" name=\"Color.Palette_byName\"></TableOccurrenceReference>
+                    <LayoutReferenceContainer value=\"5\">
+                        <LayoutReference id=\"8\" name=\"Palettes\"></LayoutReference>
+                    </LayoutReferenceContainer>
+                    <Animation name=\"Ohne\" value=\"0\"></Animation>
+                    <Options matchFoundSet=\"True\" ShowRelated=\"True\"></Options>
+                </Parameter>
+            </ParameterValues>
+        </Step>
+        ";
+
+        let expected_output = Some("Gehe zu Bezugsdatensatz [ From table: Color.Palette_byName ; Layout: \"Palettes\" ; Animation: Ohne ; Show only related records ; Match all records in current found set ]".to_string());
+        assert_eq!(sanitize(xml_input.trim()), expected_output);
+    }
+
+    #[test]
+    fn test_sanitize_new_window() {
+        let xml_input = "
+        <Step id=\"74\" name=\"Gehe zu Bezugsdatensatz\" enable=\"True\">
+            <Options>33702410</Options>
+            <ParameterValues membercount=\"1\">
+                <Parameter type=\"Related\">
+                    <TableOccurrenceReference id=\"1065117\" name=\"Syntax.GUISyntax\"></TableOccurrenceReference>
+                    <LayoutReferenceContainer value=\"5\">
+                        <LayoutReference id=\"5\" name=\"Colors\"></LayoutReference>
+                    </LayoutReferenceContainer>
+                    <WindowReference>
+                        <Style name=\"Dokument\" value=\"3606018\"></Style>
+                        <Name>
+                            <Calculation datatype=\"1\" position=\"0\">
+                                <Calculation>
+                                    <Text><![CDATA[$Fenstername]]></Text>
+                                    <ChunkList hash=\"6DBD39FA69BADF5E87A543194C96806A\">
+                                        <Chunk type=\"VariableReference\">$Fenstername</Chunk>
+                                    </ChunkList>
+                                </Calculation>
+                            </Calculation>
+                        </Name>
+                        <LayoutReferenceContainer value=\"5\"></LayoutReferenceContainer>
+                        <Bounds>
+                            <height></height>
+                            <width></width>
+                            <top></top>
+                            <left></left>
+                        </Bounds>
+                        <Options value=\"3606018\">
+                            <Close>True</Close>
+                            <Minimize>True</Minimize>
+                            <Maximize>True</Maximize>
+                            <Resize>False</Resize>
+                            <MenuBar>True</MenuBar>
+                            <Toolbar>True</Toolbar>
+                            <DimParentWindow>True</DimParentWindow>
+                        </Options>
+                    </WindowReference>
+                    <Options ShowRelated=\"True\"></Options>
+                </Parameter>
+            </ParameterValues>
+        </Step>
+        ";
+
+        let expected_output = Some("Gehe zu Bezugsdatensatz [ From table: Syntax.GUISyntax ; Layout: \"Colors\" ; Show only related records ; New window ; Style: Dokument ; Name: $Fenstername ; Resize: OFF ]".to_string());
+        assert_eq!(sanitize(xml_input.trim()), expected_output);
+    }
+}
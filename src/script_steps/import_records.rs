@@ -0,0 +1,297 @@
+use crate::calculations::calculation::Calculation;
+use crate::calculations::field_reference::FieldReference;
+use crate::calculations::path_list::UniversalPathList;
+use crate::utils::attributes::get_attribute;
+use quick_xml::events::Event;
+use quick_xml::Reader;
+
+#[derive(Debug, Default)]
+struct FieldMapping {
+    source: String,
+    kind: String,
+    target: String,
+}
+
+#[derive(Debug, Default)]
+struct XmlTrackingState {
+    in_target: bool,
+    in_map: bool,
+}
+
+const IMPORT_OPTIONS: [(&str, &str); 4] = [
+    ("doAutoEntry", "Perform auto-enter options"),
+    ("splitRepetitions", "Split repetitions"),
+    ("copyContainersAsIs", "Copy containers as is"),
+    ("matchFieldNames", "Match field names"),
+];
+
+pub fn sanitize(step: &str) -> Option<String> {
+    let mut name = String::new();
+    let mut params: Vec<String> = Vec::new();
+    let mut source = String::new();
+    let mut xml_source = String::new();
+    let mut xsl_source = String::new();
+    let mut target = String::new();
+    let mut add_as_new = false;
+    let mut import_options: Vec<String> = Vec::new();
+    let mut character_set = String::new();
+    let mut action = String::new();
+    let mut mappings: Vec<FieldMapping> = Vec::new();
+    let mut xml_pos = XmlTrackingState::default();
+
+    let mut reader = Reader::from_str(step);
+    reader.trim_text(true);
+    let mut buf: Vec<u8> = Vec::new();
+    loop {
+        match reader.read_event_into(&mut buf) {
+            Err(_) => continue,
+            Ok(Event::Eof) => break,
+            Ok(Event::Start(e)) => match e.name().as_ref() {
+                b"Step" => name = get_attribute(&e, "name").unwrap().to_string(),
+                b"Boolean" => {
+                    params.push(format!(
+                        "{}: {}",
+                        get_attribute(&e, "type").unwrap_or_default(),
+                        match get_attribute(&e, "value").unwrap_or_default().as_str() {
+                            "True" => "ON",
+                            _ => "OFF",
+                        }
+                    ));
+                }
+                b"UniversalPathList" => {
+                    source = UniversalPathList::from_xml(&mut reader, &e)
+                        .unwrap()
+                        .display()
+                        .unwrap();
+                }
+                b"XML" => xml_source = Calculation::from_xml(&mut reader, &e).unwrap(),
+                b"XSL" => xsl_source = Calculation::from_xml(&mut reader, &e).unwrap(),
+                b"ImportField" => {
+                    add_as_new = get_attribute(&e, "addasnew").unwrap_or_default() == "True"
+                }
+                b"Options" => {
+                    for (attribute, label) in IMPORT_OPTIONS {
+                        if get_attribute(&e, attribute).unwrap_or_default() == "True" {
+                            import_options.push(label.to_string());
+                        }
+                    }
+                }
+                b"Target" => xml_pos.in_target = true,
+                b"TableOccurrenceReference" if xml_pos.in_target => {
+                    target = get_attribute(&e, "name").unwrap_or_default();
+                }
+                b"Map" => {
+                    xml_pos.in_map = true;
+                    // the id belongs to the target field, source columns are numbered by position
+                    mappings.push(FieldMapping {
+                        source: (mappings.len() + 1).to_string(),
+                        kind: get_attribute(&e, "kind").unwrap_or_default(),
+                        ..Default::default()
+                    });
+                }
+                b"FieldReference" => {
+                    let field_reference = FieldReference::from_xml(&mut reader, &e)
+                        .unwrap()
+                        .display()
+                        .unwrap();
+                    if xml_pos.in_map {
+                        if let Some(mapping) = mappings.last_mut() {
+                            mapping.target = field_reference;
+                        }
+                    }
+                }
+                b"CharacterSet" => character_set = get_attribute(&e, "name").unwrap_or_default(),
+                b"action" => action = get_attribute(&e, "name").unwrap_or_default(),
+                _ => {}
+            },
+            Ok(Event::End(e)) => match e.name().as_ref() {
+                b"Target" => xml_pos.in_target = false,
+                b"Map" => xml_pos.in_map = false,
+                _ => {}
+            },
+            _ => {}
+        }
+        buf.clear()
+    }
+
+    if !source.is_empty() {
+        params.push(format!("Source: {}", source));
+    }
+    if !xml_source.is_empty() {
+        params.push(format!("XML: {}", xml_source));
+    }
+    if !xsl_source.is_empty() {
+        params.push(format!("XSL: {}", xsl_source));
+    }
+    if !target.is_empty() {
+        params.push(format!("Target: {}", target));
+        if add_as_new {
+            import_options.push("Add remaining data as new records".to_string());
+        }
+    } else if add_as_new {
+        params.push("Target: New table".to_string());
+    }
+    if !action.is_empty() {
+        params.push(format!("Action: {}", action));
+    }
+    if !character_set.is_empty() {
+        params.push(format!("Character set: {}", character_set));
+    }
+    if !import_options.is_empty() {
+        params.push(import_options.join(", "));
+    }
+
+    let field_mapping = mappings
+        .iter()
+        .filter_map(|mapping| match mapping.kind.as_str() {
+            "0" => Some(format!("{} → {}", mapping.source, mapping.target)),
+            "2" => Some(format!("{} = {}", mapping.source, mapping.target)),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    if !field_mapping.is_empty() {
+        params.push(format!("Field mapping: {}", field_mapping.join(", ")));
+    }
+
+    if name.is_empty() {
+        None
+    } else {
+        Some(format!("{} [ {} ]", name, params.join(" ; ")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sanitize_with_dialog() {
+        let xml_input = "
+        <Step id=\"35\" name=\"Datensätze importieren\" enable=\"True\">
+            <ParameterValues membercount=\"2\">
+                <Parameter type=\"Boolean\">
+                    <Boolean type=\"SSL-Zertifikate verifizieren\" id=\"268435456\" value=\"False\"></Boolean>
+                </Parameter>
+                <Parameter type=\"Boolean\">
+                    <Boolean type=\"Mit Dialog\" id=\"128\" value=\"True\"></Boolean>
+                </Parameter>
+            </ParameterValues>
+        </Step>
+        ";
+
+        let expected_output = Some(
+            "Datensätze importieren [ SSL-Zertifikate verifizieren: OFF ; Mit Dialog: ON ]"
+                .to_string(),
+        );
+        assert_eq!(sanitize(xml_input.trim()), expected_output);
+    }
+
+    #[test]
+    fn test_sanitize_field_mapping() {
+        let xml_input = "
+        <Step id=\"35\" name=\"Datensätze importieren\" enable=\"True\">
+            <Options>33587360</Options>
+            <ParameterValues membercount=\"4\">
+                <Parameter type=\"Boolean\">
+                    <Boolean type=\"SSL-Zertifikate verifizieren\" id=\"268435456\" value=\"False\"></Boolean>
+                </Parameter>
+                <Parameter type=\"Boolean\">
+                    <Boolean type=\"Mit Dialog\" id=\"128\" value=\"False\"></Boolean>
+                </Parameter>
+                <Parameter type=\"DataSourceReference\">
+                    <DataSourceReference fileType=\"FMPR\" isPredefined=\"False\" delimiter=\"1\" value=\"1\">
+                        <UniversalPathList fileType=\"FMPR\" membercount=\"1\">
+                            <ObjectList>
+                                <Location><![CDATA[file:fmSyntaxColorizer.fmp12]]></Location>
+                            </ObjectList>
+                        </UniversalPathList>
+                    </DataSourceReference>
+                </Parameter>
+                <Parameter type=\"ImportField\">
+                    <ImportField addasnew=\"False\" isPredefined=\"False\" delimiter=\"0\" fieldNameRow=\"0\">
+                        <Options copyContainersAsIs=\"False\" matchFieldNames=\"False\" doAutoEntry=\"True\" splitRepetitions=\"False\"></Options>
+                        <Target>
+                            <TableOccurrenceReference id=\"1065123\" name=\"_Home\"></TableOccurrenceReference>
+                        </Target>
+                        <Field membercount=\"3\">
+                            <Map New=\"False\" name=\"Quellfeld 1 abgleichen mit \" id=\"1\" kind=\"2\" Options=\"0\">
+                                <FieldReference id=\"1\" name=\"__ID\">
+                                    <TableOccurrenceReference id=\"1065123\" name=\"_Home\"></TableOccurrenceReference>
+                                </FieldReference>
+                            </Map>
+                            <Map New=\"False\" name=\"Quellfeld 2 importieren in \" id=\"2\" kind=\"0\" Options=\"0\">
+                                <FieldReference id=\"2\" name=\"_gHomeScreenInfo\">
+                                    <TableOccurrenceReference id=\"1065123\" name=\"_Home\"></TableOccurrenceReference>
+                                </FieldReference>
+                            </Map>
+                            <Map New=\"False\" name=\"Quellfeld 3 nicht importiert in\" id=\"3\" kind=\"1\" Options=\"0\">
+                                <FieldReference id=\"3\" name=\"_gLanguage\">
+                                    <TableOccurrenceReference id=\"1065123\" name=\"_Home\"></TableOccurrenceReference>
+                                </FieldReference>
+                            </Map>
+                        </Field>
+                        <CharacterSet name=\"UTF-8\" value=\"5\"></CharacterSet>
+                        <action name=\"Aktualisieren\" value=\"2\"></action>
+                    </ImportField>
+                </Parameter>
+            </ParameterValues>
+        </Step>
+        ";
+
+        let expected_output = Some("Datensätze importieren [ SSL-Zertifikate verifizieren: OFF ; Mit Dialog: OFF ; Source: \"file:fmSyntaxColorizer.fmp12\" ; Target: _Home ; Action: Aktualisieren ; Character set: UTF-8 ; Perform auto-enter options ; Field mapping: 1 = _Home::__ID, 2 → _Home::_gHomeScreenInfo ]".to_string());
+        assert_eq!(sanitize(xml_input.trim()), expected_output);
+    }
+
+    #[test]
+    fn test_sanitize_update_with_add_as_new() {
+        let xml_input = "
+        <Step id=\"35\" name=\"Datensätze importieren\" enable=\"True\">
+            <ParameterValues membercount=\"1\">
+                <Parameter type=\"ImportField\">
+                    <ImportField addasnew=\"True\" isPredefined=\"False\" delimiter=\"0\" fieldNameRow=\"0\">
+                        <Options copyContainersAsIs=\"False\" matchFieldNames=\"False\" doAutoEntry=\"False\" splitRepetitions=\"False\"></Options>
+                        <Target>
+                            <TableOccurrenceReference id=\"1065130\" name=\"_Color\"></TableOccurrenceReference>
+                        </Target>
+                        <Field membercount=\"2\">
+                            <Map New=\"False\" name=\"Quellfeld 1 abgleichen mit \" id=\"14\" kind=\"2\" Options=\"0\">
+                                <FieldReference id=\"14\" name=\"ID\">
+                                    <TableOccurrenceReference id=\"1065130\" name=\"_Color\"></TableOccurrenceReference>
+                                </FieldReference>
+                            </Map>
+                            <Map New=\"False\" name=\"Quellfeld 2 importieren in \" id=\"15\" kind=\"0\" Options=\"0\">
+                                <FieldReference id=\"15\" name=\"PaletteName\">
+                                    <TableOccurrenceReference id=\"1065130\" name=\"_Color\"></TableOccurrenceReference>
+                                </FieldReference>
+                            </Map>
+                        </Field>
+                        <action name=\"Aktualisieren\" value=\"2\"></action>
+                    </ImportField>
+                </Parameter>
+            </ParameterValues>
+        </Step>
+        ";
+
+        let expected_output = Some("Datensätze importieren [ Target: _Color ; Action: Aktualisieren ; Add remaining data as new records ; Field mapping: 1 = _Color::ID, 2 → _Color::PaletteName ]".to_string());
+        assert_eq!(sanitize(xml_input.trim()), expected_output);
+    }
+
+    #[test]
+    fn test_sanitize_new_table() {
+        let xml_input = "
+        <Step id=\"35\" name=\"Datensätze importieren\" enable=\"True\">
+            <ParameterValues membercount=\"1\">
+                <Parameter type=\"ImportField\">
+                    <ImportField addasnew=\"True\" isPredefined=\"False\" delimiter=\"0\" fieldNameRow=\"0\">
+                        <action name=\"Hinzufügen\" value=\"0\"></action>
+                    </ImportField>
+                </Parameter>
+            </ParameterValues>
+        </Step>
+        ";
+
+        let expected_output =
+            Some("Datensätze importieren [ Target: New table ; Action: Hinzufügen ]".to_string());
+        assert_eq!(sanitize(xml_input.trim()), expected_output);
+    }
+}
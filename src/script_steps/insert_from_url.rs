@@ -0,0 +1,178 @@
+use crate::calculations::calculation::Calculation;
+use crate::calculations::field_reference::FieldReferenceParameter;
+use crate::utils::attributes::get_attribute;
+use quick_xml::events::Event;
+use quick_xml::Reader;
+
+pub fn sanitize(step: &str) -> Option<String> {
+    let mut name = String::new();
+    let mut options: Vec<(String, bool)> = Vec::new();
+    let mut target = String::new();
+    let mut url = String::new();
+    let mut auto_encode = true;
+    let mut curl_options = String::new();
+
+    let mut reader = Reader::from_str(step);
+    reader.trim_text(true);
+    let mut buf: Vec<u8> = Vec::new();
+    loop {
+        match reader.read_event_into(&mut buf) {
+            Err(_) => continue,
+            Ok(Event::Eof) => break,
+            Ok(Event::Start(e)) => match e.name().as_ref() {
+                b"Step" => name = get_attribute(&e, "name").unwrap().to_string(),
+                b"Boolean" => options.push((
+                    get_attribute(&e, "type").unwrap_or_default(),
+                    get_attribute(&e, "value").unwrap_or_default() == "True",
+                )),
+                b"Parameter" => match get_attribute(&e, "type").unwrap_or_default().as_str() {
+                    "Target" => {
+                        target = FieldReferenceParameter::from_xml(&mut reader, &e)
+                            .unwrap()
+                            .display()
+                            .unwrap()
+                    }
+                    "Calculation" => {
+                        curl_options = Calculation::from_xml(&mut reader, &e).unwrap();
+                    }
+                    _ => {}
+                },
+                b"URL" => {
+                    auto_encode = get_attribute(&e, "autoEncode").unwrap_or_default() != "False";
+                    url = Calculation::from_xml(&mut reader, &e).unwrap();
+                }
+                _ => {}
+            },
+            _ => {}
+        }
+        buf.clear()
+    }
+
+    let mut params: Vec<String> = Vec::new();
+    for (label, value) in &options {
+        params.push(format!(
+            "{}: {}",
+            label,
+            match value {
+                true => "ON",
+                false => "OFF",
+            }
+        ));
+    }
+    if !target.is_empty() {
+        params.push(format!("Target: {}", target));
+    }
+    if !url.is_empty() {
+        params.push(format!("URL: {}", url));
+    }
+    if !auto_encode {
+        params.push("Auto encode URL: OFF".to_string());
+    }
+    if !curl_options.is_empty() {
+        params.push(format!("cURL options: {}", curl_options));
+    }
+
+    if name.is_empty() {
+        None
+    } else {
+        Some(format!("{} [ {} ]", name, params.join(" ; ")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sanitize_variable_target() {
+        let xml_input = "
+        <Step id=\"160\" name=\"Aus URL einfügen\" enable=\"True\">
+            <Options>20613</Options>
+            <ParameterValues membercount=\"6\">
+                <Parameter type=\"Boolean\">
+                    <Boolean type=\"SSL-Zertifikate verifizieren\" id=\"268435456\" value=\"False\"></Boolean>
+                </Parameter>
+                <Parameter type=\"Boolean\">
+                    <Boolean type=\"Auswahl\" id=\"4096\" value=\"True\"></Boolean>
+                </Parameter>
+                <Parameter type=\"Boolean\">
+                    <Boolean type=\"Mit Dialog\" id=\"128\" value=\"False\"></Boolean>
+                </Parameter>
+                <Parameter type=\"Target\">
+                    <Variable value=\"$$my.result\">
+                        <repetition value=\"1\"></repetition>
+                    </Variable>
+                </Parameter>
+                <Parameter type=\"URL\">
+                    <URL autoEncode=\"True\">
+                        <Calculation datatype=\"1\" position=\"0\">
+                            <Calculation>
+                                <Text><![CDATA[$$server & $endpoint]]></Text>
+                                <ChunkList hash=\"9EA92AEF077FA44AE68308694556F8CB\">
+                                    <Chunk type=\"VariableReference\">$$server</Chunk>
+                                    <Chunk type=\"NoRef\"> &amp; </Chunk>
+                                    <Chunk type=\"VariableReference\">$endpoint</Chunk>
+                                </ChunkList>
+                            </Calculation>
+                        </Calculation>
+                    </URL>
+                </Parameter>
+                <Parameter type=\"Calculation\">
+                    <Calculation datatype=\"1\" position=\"1\">
+                        <Calculation>
+                            <Text><![CDATA[$curloptions]]></Text>
+                            <ChunkList hash=\"205B4DA1BAD160DEDA31FE70167BBA78\">
+                                <Chunk type=\"VariableReference\">$curloptions</Chunk>
+                            </ChunkList>
+                        </Calculation>
+                    </Calculation>
+                </Parameter>
+            </ParameterValues>
+        </Step>
+        ";
+
+        let expected_output = Some("Aus URL einfügen [ SSL-Zertifikate verifizieren: OFF ; Auswahl: ON ; Mit Dialog: OFF ; Target: $$my.result ; URL: $$server & $endpoint ; cURL options: $curloptions ]".to_string());
+        assert_eq!(sanitize(xml_input.trim()), expected_output);
+    }
+
+    #[test]
+    fn test_sanitize_field_target() {
+        let xml_input = "
+        <Step id=\"160\" name=\"Aus URL einfügen\" enable=\"True\">
+            <Options>20865</Options>
+            <ParameterValues membercount=\"5\">
+                <Parameter type=\"Boolean\">
+                    <Boolean type=\"SSL-Zertifikate verifizieren\" id=\"268435456\" value=\"True\"></Boolean>
+                </Parameter>
+                <Parameter type=\"Boolean\">
+                    <Boolean type=\"Auswahl\" id=\"4096\" value=\"False\"></Boolean>
+                </Parameter>
+                <Parameter type=\"Boolean\">
+                    <Boolean type=\"Mit Dialog\" id=\"128\" value=\"False\"></Boolean>
+                </Parameter>
+                <Parameter type=\"Target\">
+                    <FieldReference id=\"14\" name=\"privateKey_r\">
+                        <repetition value=\"1\"></repetition>
+                        <TableOccurrenceReference id=\"1065117\" name=\"PUB__publickeys\"></TableOccurrenceReference>
+                    </FieldReference>
+                </Parameter>
+                <Parameter type=\"URL\">
+                    <URL autoEncode=\"False\">
+                        <Calculation datatype=\"1\" position=\"0\">
+                            <Calculation>
+                                <Text><![CDATA[$this.url]]></Text>
+                                <ChunkList hash=\"40717649757E4E12FDD2F736F334B9D5\">
+                                    <Chunk type=\"VariableReference\">$this.url</Chunk>
+                                </ChunkList>
+                            </Calculation>
+                        </Calculation>
+                    </URL>
+                </Parameter>
+            </ParameterValues>
+        </Step>
+        ";
+
+        let expected_output = Some("Aus URL einfügen [ SSL-Zertifikate verifizieren: ON ; Auswahl: OFF ; Mit Dialog: OFF ; Target: PUB__publickeys::privateKey_r ; URL: $this.url ; Auto encode URL: OFF ]".to_string());
+        assert_eq!(sanitize(xml_input.trim()), expected_output);
+    }
+}
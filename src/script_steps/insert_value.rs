@@ -0,0 +1,224 @@
+use crate::calculations::calculation::Calculation;
+use crate::calculations::field_reference::FieldReferenceParameter;
+use crate::calculations::path_list::UniversalPathList;
+use crate::utils::attributes::get_attribute;
+use html_escape::decode_html_entities;
+use quick_xml::events::Event;
+use quick_xml::Reader;
+
+pub fn sanitize(step: &str) -> Option<String> {
+    let mut name = String::new();
+    let mut params: Vec<String> = Vec::new();
+    let mut target = String::new();
+    let mut value = String::new();
+    let mut path_list = UniversalPathList::default();
+
+    let mut reader = Reader::from_str(step);
+    reader.trim_text(true);
+    let mut buf: Vec<u8> = Vec::new();
+    loop {
+        match reader.read_event_into(&mut buf) {
+            Err(_) => continue,
+            Ok(Event::Eof) => break,
+            Ok(Event::Start(e)) => match e.name().as_ref() {
+                b"Step" => name = get_attribute(&e, "name").unwrap().to_string(),
+                b"Boolean" => {
+                    params.push(format!(
+                        "{}: {}",
+                        get_attribute(&e, "type").unwrap_or_default(),
+                        match get_attribute(&e, "value").unwrap_or_default().as_str() {
+                            "True" => "ON",
+                            _ => "OFF",
+                        }
+                    ));
+                }
+                b"Parameter" => match get_attribute(&e, "type").unwrap_or_default().as_str() {
+                    "Target" => {
+                        target = FieldReferenceParameter::from_xml(&mut reader, &e)
+                            .unwrap()
+                            .display()
+                            .unwrap()
+                    }
+                    "Calculation" => value = Calculation::from_xml(&mut reader, &e).unwrap(),
+                    _ => {}
+                },
+                b"Text" => {
+                    let text = get_attribute(&e, "value").unwrap_or_default();
+                    if !text.is_empty() {
+                        value = format!("\"{}\"", decode_html_entities(&text));
+                    }
+                }
+                b"UniversalPathList" => {
+                    path_list = UniversalPathList::from_xml(&mut reader, &e).unwrap();
+                }
+                _ => {}
+            },
+            _ => {}
+        }
+        buf.clear()
+    }
+
+    if !target.is_empty() {
+        params.push(format!("Target: {}", target));
+    }
+    if !value.is_empty() {
+        params.push(value);
+    }
+    if !path_list.paths.is_empty() {
+        params.push(format!("Path: {}", path_list.display().unwrap()));
+    }
+
+    if name.is_empty() {
+        None
+    } else if params.is_empty() {
+        Some(format!("{} []", name))
+    } else {
+        Some(format!("{} [ {} ]", name, params.join(" ; ")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sanitize_insert_calculated_result() {
+        let xml_input = "
+        <Step id=\"77\" name=\"Berechneten Wert einfügen\" enable=\"True\">
+            <Options>20485</Options>
+            <ParameterValues membercount=\"3\">
+                <Parameter type=\"Boolean\">
+                    <Boolean type=\"Auswahl\" id=\"4096\" value=\"True\"></Boolean>
+                </Parameter>
+                <Parameter type=\"Target\">
+                    <FieldReference id=\"9\" name=\"_gEmptyField\">
+                        <repetition value=\"1\"></repetition>
+                        <TableOccurrenceReference id=\"1065123\" name=\"_Home\"></TableOccurrenceReference>
+                    </FieldReference>
+                </Parameter>
+                <Parameter type=\"Calculation\">
+                    <Calculation datatype=\"1\" position=\"0\">
+                        <Calculation>
+                            <Text><![CDATA[\"whatever\"]]></Text>
+                            <ChunkList hash=\"A725544539F321AB1CBCB4863225AE96\">
+                                <Chunk type=\"NoRef\">&quot;whatever&quot;</Chunk>
+                            </ChunkList>
+                        </Calculation>
+                    </Calculation>
+                </Parameter>
+            </ParameterValues>
+        </Step>
+        ";
+
+        let expected_output = Some("Berechneten Wert einfügen [ Auswahl: ON ; Target: _Home::_gEmptyField ; \"whatever\" ]".to_string());
+        assert_eq!(sanitize(xml_input.trim()), expected_output);
+    }
+
+    #[test]
+    fn test_sanitize_insert_text() {
+        let xml_input = "
+        <Step id=\"61\" name=\"Text einfügen\" enable=\"True\">
+            <Options>4101</Options>
+            <ParameterValues membercount=\"3\">
+                <Parameter type=\"Boolean\">
+                    <Boolean type=\"Auswahl\" id=\"4096\" value=\"True\"></Boolean>
+                </Parameter>
+                <Parameter type=\"Target\">
+                    <Variable value=\"$TargetVar\">
+                        <repetition value=\"1\"></repetition>
+                    </Variable>
+                </Parameter>
+                <Parameter type=\"Text\">
+                    <Text value=\"$ThisIsNotAVariable\"></Text>
+                </Parameter>
+            </ParameterValues>
+        </Step>
+        ";
+
+        let expected_output = Some(
+            "Text einfügen [ Auswahl: ON ; Target: $TargetVar ; \"$ThisIsNotAVariable\" ]"
+                .to_string(),
+        );
+        assert_eq!(sanitize(xml_input.trim()), expected_output);
+    }
+
+    #[test]
+    fn test_sanitize_insert_text_with_entities() {
+        let xml_input = "
+        <Step id=\"61\" name=\"Text einfügen\" enable=\"True\">
+            <ParameterValues membercount=\"2\">
+                <Parameter type=\"Target\">
+                    <Variable value=\"$Text\">
+                        <repetition value=\"1\"></repetition>
+                    </Variable>
+                </Parameter>
+                <Parameter type=\"Text\">
+                    <Text value=\"A &amp; B&#13;C\"></Text>
+                </Parameter>
+            </ParameterValues>
+        </Step>
+        ";
+
+        let expected_output = Some("Text einfügen [ Target: $Text ; \"A & B\rC\" ]".to_string());
+        assert_eq!(sanitize(xml_input.trim()), expected_output);
+    }
+
+    #[test]
+    fn test_sanitize_insert_file_without_text() {
+        let xml_input = "
+        <Step id=\"131\" name=\"Datei einfügen\" enable=\"True\">
+            <ParameterValues membercount=\"1\">
+                <Parameter type=\"Text\">
+                    <Text value=\"\"></Text>
+                </Parameter>
+            </ParameterValues>
+        </Step>
+        ";
+
+        let expected_output = Some("Datei einfügen []".to_string());
+        assert_eq!(sanitize(xml_input.trim()), expected_output);
+    }
+
+    #[test]
+    fn test_sanitize_insert_current_date() {
+        let xml_input = "
+        <Step id=\"13\" name=\"Systemdatum einfügen\" enable=\"True\">
+            <Options>4096</Options>
+            <ParameterValues membercount=\"1\">
+                <Parameter type=\"Boolean\">
+                    <Boolean type=\"Auswahl\" id=\"4096\" value=\"True\"></Boolean>
+                </Parameter>
+            </ParameterValues>
+        </Step>
+        ";
+
+        let expected_output = Some("Systemdatum einfügen [ Auswahl: ON ]".to_string());
+        assert_eq!(sanitize(xml_input.trim()), expected_output);
+    }
+
+    #[test]
+    fn test_sanitize_insert_picture() {
+        let xml_input = "
+        <Step id=\"56\" name=\"Bild einfügen\" enable=\"True\">
+            <Options>32</Options>
+            <ParameterValues membercount=\"2\">
+                <Parameter type=\"Boolean\">
+                    <Boolean type=\"Nur Verweis speichern\" id=\"2048\" value=\"False\"></Boolean>
+                </Parameter>
+                <Parameter type=\"UniversalPathList\">
+                    <UniversalPathList membercount=\"1\">
+                        <ObjectList>
+                            <Location><![CDATA[$ThisIsAPathVar]]></Location>
+                        </ObjectList>
+                    </UniversalPathList>
+                </Parameter>
+            </ParameterValues>
+        </Step>
+        ";
+
+        let expected_output = Some(
+            "Bild einfügen [ Nur Verweis speichern: OFF ; Path: \"$ThisIsAPathVar\" ]".to_string(),
+        );
+        assert_eq!(sanitize(xml_input.trim()), expected_output);
+    }
+}
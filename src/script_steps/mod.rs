@@ -1,23 +1,46 @@
+mod account;
 mod close_window;
 mod comment;
 mod commit;
 pub(crate) mod constants;
+mod data_file;
+mod execute_sql;
 mod exit_script;
+mod export_records;
+mod field_with_option;
 mod go_to_field;
 mod go_to_layout;
 mod go_to_object;
 mod go_to_portal_row;
 mod go_to_record;
+mod go_to_related_record;
 mod if_start;
+mod import_records;
+mod insert_from_url;
+mod insert_value;
 mod is_enabled;
+mod move_resize_window;
 mod new_window;
 mod omit_multiple_records;
+mod parameter_list;
+mod pause_resume_script;
+mod perform_applescript;
 mod perform_find;
-mod perform_script;
+pub(crate) mod perform_script;
 mod primitive;
 mod primitive_with_boolean;
+mod primitive_with_option;
 mod refresh_object;
 mod replace_field_contents;
+mod revert_transaction;
 pub(crate) mod sanitizer;
+mod save_records_as;
+mod select_window;
+mod send_event;
+mod send_mail;
+mod set_field_by_name;
 mod set_field_data;
 mod set_variable;
+mod set_window_title;
+mod show_custom_dialog;
+mod sort_records;
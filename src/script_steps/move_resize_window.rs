@@ -0,0 +1,100 @@
+use crate::calculations::window_reference::WindowReference;
+use crate::utils::attributes::get_attribute;
+use quick_xml::events::Event;
+use quick_xml::Reader;
+
+pub fn sanitize(step: &str) -> Option<String> {
+    let mut name = String::new();
+    let mut window_reference = WindowReference::default();
+
+    let mut reader = Reader::from_str(step);
+    reader.trim_text(true);
+    let mut buf: Vec<u8> = Vec::new();
+    loop {
+        match reader.read_event_into(&mut buf) {
+            Err(_) => continue,
+            Ok(Event::Eof) => break,
+            Ok(Event::Start(e)) => match e.name().as_ref() {
+                b"Step" => name = get_attribute(&e, "name").unwrap().to_string(),
+                b"WindowReference" => {
+                    window_reference = WindowReference::from_xml(&mut reader, &e).unwrap()
+                }
+                _ => {}
+            },
+            _ => {}
+        }
+        buf.clear()
+    }
+
+    let mut params: Vec<String> = vec![window_reference.display().unwrap()];
+    let bounds = &window_reference.bounds;
+    if !bounds.height.is_empty() {
+        params.push(format!("Height: {}", bounds.height));
+    }
+    if !bounds.width.is_empty() {
+        params.push(format!("Width: {}", bounds.width));
+    }
+    if !bounds.top.is_empty() {
+        params.push(format!("Top: {}", bounds.top));
+    }
+    if !bounds.left.is_empty() {
+        params.push(format!("Left: {}", bounds.left));
+    }
+
+    if name.is_empty() {
+        None
+    } else {
+        Some(format!("{} [ {} ]", name, params.join(" ; ")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sanitize() {
+        let xml_input = "
+        <Step id=\"119\" name=\"Fensterposition/-größe ändern\" enable=\"True\">
+            <Options>-2147467262</Options>
+            <ParameterValues membercount=\"1\">
+                <Parameter type=\"WindowReference\">
+                    <WindowReference>
+                        <Select kind=\"0\" type=\"current\"></Select>
+                        <Bounds>
+                            <height></height>
+                            <width>
+                                <Calculation datatype=\"1\" position=\"2\">
+                                    <Calculation>
+                                        <Text><![CDATA[$FensterBreite]]></Text>
+                                        <ChunkList hash=\"3E2E5C0B4D4B7F0C8C1E2A9C2C4B5A6D\">
+                                            <Chunk type=\"VariableReference\">$FensterBreite</Chunk>
+                                        </ChunkList>
+                                    </Calculation>
+                                </Calculation>
+                            </width>
+                            <top>
+                                <Calculation datatype=\"1\" position=\"3\">
+                                    <Calculation>
+                                        <Text><![CDATA[1]]></Text>
+                                        <ChunkList hash=\"A93F17BC54CA1958073B692697D5ED21\">
+                                            <Chunk type=\"NoRef\">1</Chunk>
+                                        </ChunkList>
+                                    </Calculation>
+                                </Calculation>
+                            </top>
+                            <left></left>
+                        </Bounds>
+                    </WindowReference>
+                </Parameter>
+            </ParameterValues>
+        </Step>
+        ";
+
+        let expected_output = Some(
+            "Fensterposition/-größe ändern [ Current window ; Width: $FensterBreite ; Top: 1 ]"
+                .to_string(),
+        );
+        assert_eq!(sanitize(xml_input.trim()), expected_output);
+    }
+}
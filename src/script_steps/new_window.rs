@@ -17,10 +17,10 @@ struct WindowOptions {
 }
 
 #[derive(Debug, Default)]
-struct WindowReference {
+pub(crate) struct WindowReference {
     style: String,
     name: String,
-    layout_reference: String,
+    pub(crate) layout_reference: String,
     bounds: Bounds,
     options: WindowOptions,
 }
@@ -54,6 +54,17 @@ const WINDOW_NAME_PATH: &str =
     "Step/ParameterValues/Parameter/WindowReference/Name/Calculation/Calculation/Text";
 
 pub fn sanitize(step: &str) -> Option<String> {
+    let (name, window) = parse(step);
+
+    if name.is_empty() {
+        println!("empty primitive");
+        None
+    } else {
+        Some(format!("{} [ {} ]", name, window.params().join(" ; ")))
+    }
+}
+
+pub(crate) fn parse(step: &str) -> (String, WindowReference) {
     let mut name = String::new();
     let mut window = WindowReference::default();
     let mut xml_pos = XmlTrackingState::default();
@@ -80,7 +91,8 @@ pub fn sanitize(step: &str) -> Option<String> {
                             LayoutReferenceContainer::from_xml(&mut reader, &e)
                                 .unwrap()
                                 .display()
-                                .unwrap()
+                                .unwrap();
+                        current_path.pop();
                     }
                     b"Options" => xml_pos.in_options = true,
                     b"Close" => xml_pos.in_close_option = true,
@@ -178,55 +190,56 @@ pub fn sanitize(step: &str) -> Option<String> {
         buf.clear()
     }
 
-    let mut params: Vec<String> = Vec::new();
+    (name, window)
+}
 
-    if !window.style.is_empty() {
-        let style_formatted = format!("Style: {}", window.style);
-        params.push(style_formatted);
-    }
-    if !window.name.is_empty() {
-        let calculation_formatted = format!("Name: {}", window.name);
-        params.push(calculation_formatted);
-    }
-    if !window.layout_reference.is_empty() {
-        params.push(window.layout_reference)
-    }
-    if window.bounds.height.is_some() {
-        params.push(format!("Height: {}", window.bounds.height.unwrap()));
-    }
-    if window.bounds.width.is_some() {
-        params.push(format!("Width: {}", window.bounds.width.unwrap()));
-    }
-    if window.bounds.top.is_some() {
-        params.push(format!("Top: {}", window.bounds.top.unwrap()));
-    }
-    if window.bounds.left.is_some() {
-        params.push(format!("Left: {}", window.bounds.left.unwrap()));
-    }
-    if !window.options.close {
-        params.push("Close: OFF".to_string());
-    }
-    if !window.options.minimize {
-        params.push("Minimize: OFF".to_string());
-    }
-    if !window.options.maximize {
-        params.push("Maximize: OFF".to_string());
-    }
-    if !window.options.resize {
-        params.push("Resize: OFF".to_string());
-    }
-    if !window.options.menu_bar {
-        params.push("Menu: OFF".to_string());
-    }
-    if !window.options.toolbar {
-        params.push("Toolbar: OFF".to_string());
-    }
+impl WindowReference {
+    pub(crate) fn params(&self) -> Vec<String> {
+        let mut params: Vec<String> = Vec::new();
 
-    if name.is_empty() {
-        println!("empty primitive");
-        None
-    } else {
-        Some(format!("{} [ {} ]", name, params.join(" ; ")))
+        if !self.style.is_empty() {
+            let style_formatted = format!("Style: {}", self.style);
+            params.push(style_formatted);
+        }
+        if !self.name.is_empty() {
+            let calculation_formatted = format!("Name: {}", self.name);
+            params.push(calculation_formatted);
+        }
+        if !self.layout_reference.is_empty() {
+            params.push(self.layout_reference.to_string())
+        }
+        if self.bounds.height.is_some() {
+            params.push(format!("Height: {}", self.bounds.height.as_ref().unwrap()));
+        }
+        if self.bounds.width.is_some() {
+            params.push(format!("Width: {}", self.bounds.width.as_ref().unwrap()));
+        }
+        if self.bounds.top.is_some() {
+            params.push(format!("Top: {}", self.bounds.top.as_ref().unwrap()));
+        }
+        if self.bounds.left.is_some() {
+            params.push(format!("Left: {}", self.bounds.left.as_ref().unwrap()));
+        }
+        if !self.options.close {
+            params.push("Close: OFF".to_string());
+        }
+        if !self.options.minimize {
+            params.push("Minimize: OFF".to_string());
+        }
+        if !self.options.maximize {
+            params.push("Maximize: OFF".to_string());
+        }
+        if !self.options.resize {
+            params.push("Resize: OFF".to_string());
+        }
+        if !self.options.menu_bar {
+            params.push("Menu: OFF".to_string());
+        }
+        if !self.options.toolbar {
+            params.push("Toolbar: OFF".to_string());
+        }
+
+        params
     }
 }
 
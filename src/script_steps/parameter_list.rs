@@ -0,0 +1,408 @@
+use crate::calculations::calculation::Calculation;
+use crate::calculations::field_reference::FieldReference;
+use crate::calculations::path_list::UniversalPathList;
+use crate::utils::attributes::get_attribute;
+use crate::utils::xml_utils::{local_name_to_string, text_to_string};
+use html_escape::decode_html_entities;
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+
+const UNLABELED_PARAMETER_TYPES: [&str; 5] =
+    ["", "List", "Calculation", "Boolean", "FieldReference"];
+
+/// Turns a parameter type like `FunctionName` into `Function name`.
+fn readable(parameter_type: &str) -> String {
+    let mut label = String::new();
+    let mut previous = ' ';
+    for c in parameter_type.chars() {
+        if label.is_empty() {
+            label.extend(c.to_uppercase());
+        } else if c.is_uppercase() && previous.is_lowercase() {
+            label.push(' ');
+            label.extend(c.to_lowercase());
+        } else {
+            label.push(c);
+        }
+        previous = c;
+    }
+    label
+}
+
+fn push_labeled(params: &mut Vec<String>, parameter_types: &[String], value: String) {
+    if value.is_empty() {
+        return;
+    }
+
+    match parameter_types.last() {
+        Some(parameter_type) if !UNLABELED_PARAMETER_TYPES.contains(&parameter_type.as_str()) => {
+            params.push(format!("{}: {}", readable(parameter_type), value))
+        }
+        _ => params.push(value),
+    }
+}
+
+/// Renders the parameters that are fully described by their attributes, these may be self-closing.
+fn push_attributes(e: &BytesStart, params: &mut Vec<String>, parameter_types: &[String]) {
+    match e.name().as_ref() {
+        b"Boolean" => {
+            let value = match get_attribute(e, "value").unwrap_or_default().as_str() {
+                "True" => "ON",
+                _ => "OFF",
+            };
+            params.push(match get_attribute(e, "type").unwrap_or_default().trim() {
+                "" => value.to_string(),
+                boolean_type => format!("{}: {}", boolean_type, value),
+            });
+        }
+        b"List" => push_labeled(
+            params,
+            parameter_types,
+            decode_html_entities(&get_attribute(e, "name").unwrap_or_default()).to_string(),
+        ),
+        b"Variable" => push_labeled(
+            params,
+            parameter_types,
+            get_attribute(e, "value").unwrap_or_default(),
+        ),
+        b"ScriptReference" => {
+            let script = get_attribute(e, "name").unwrap_or_default();
+            if !script.is_empty() {
+                params.push(format!("Script: \"{}\"", decode_html_entities(&script)));
+            }
+        }
+        b"DataSourceReference" => {
+            let data_source = get_attribute(e, "name").unwrap_or_default();
+            if !data_source.is_empty() {
+                params.push(format!("File: \"{}\"", decode_html_entities(&data_source)));
+            }
+        }
+        _ => {}
+    }
+}
+
+pub fn sanitize(step: &str) -> Option<String> {
+    sanitize_with_coverage(step).0
+}
+
+/// Same as [`sanitize`], but also reports whether every parameter of the step
+/// was rendered. Steps without any parameter count as covered.
+pub fn sanitize_with_coverage(step: &str) -> (Option<String>, bool) {
+    let mut name = String::new();
+    let mut params: Vec<String> = Vec::new();
+    let mut parameter_types: Vec<String> = Vec::new();
+    let mut current_path: Vec<String> = Vec::new();
+    let mut parameter_start = 0;
+    let mut all_rendered = true;
+
+    let mut reader = Reader::from_str(step);
+    reader.trim_text(true);
+    let mut buf: Vec<u8> = Vec::new();
+    loop {
+        match reader.read_event_into(&mut buf) {
+            Err(_) => continue,
+            Ok(Event::Eof) => break,
+            Ok(Event::Start(e)) => match e.name().as_ref() {
+                b"Step" => name = get_attribute(&e, "name").unwrap().to_string(),
+                b"Calculation" => {
+                    let calculation = Calculation::from_xml(&mut reader, &e).unwrap();
+                    push_labeled(&mut params, &parameter_types, calculation);
+                }
+                b"FieldReference" => {
+                    let field_reference = FieldReference::from_xml(&mut reader, &e)
+                        .unwrap()
+                        .display()
+                        .unwrap();
+                    push_labeled(&mut params, &parameter_types, field_reference);
+                }
+                b"UniversalPathList" => {
+                    let path_list = UniversalPathList::from_xml(&mut reader, &e).unwrap();
+                    if !path_list.paths.is_empty() {
+                        params.push(format!("File: {}", path_list.display().unwrap()));
+                    }
+                }
+                b"Parameter" => {
+                    if parameter_types.is_empty() {
+                        parameter_start = params.len();
+                    }
+                    current_path.push(local_name_to_string(e.name().as_ref()));
+                    parameter_types.push(get_attribute(&e, "type").unwrap_or_default());
+                }
+                _ => {
+                    current_path.push(local_name_to_string(e.name().as_ref()));
+                    push_attributes(&e, &mut params, &parameter_types);
+                }
+            },
+            Ok(Event::Empty(e)) if e.name().as_ref() == b"Step" => {
+                name = get_attribute(&e, "name").unwrap().to_string()
+            }
+            Ok(Event::Empty(e)) => push_attributes(&e, &mut params, &parameter_types),
+            Ok(Event::Text(e)) if current_path.last().map(String::as_str) == Some("Parameter") => {
+                push_labeled(&mut params, &parameter_types, text_to_string(&e));
+            }
+            Ok(Event::End(e)) => {
+                if e.name().as_ref() == b"Parameter" {
+                    parameter_types.pop();
+                    if parameter_types.is_empty() && params.len() == parameter_start {
+                        all_rendered = false;
+                    }
+                }
+                current_path.pop();
+            }
+            _ => {}
+        }
+        buf.clear()
+    }
+
+    let sanitized = if name.is_empty() {
+        None
+    } else if params.is_empty() {
+        Some(format!("{} []", name))
+    } else {
+        Some(format!("{} [ {} ]", name, params.join(" ; ")))
+    };
+
+    (sanitized, all_rendered)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sanitize_insert_from_device() {
+        let xml_input = "
+        <Step id=\"161\" name=\"Von Gerät einfügen\" enable=\"True\">
+            <Options>16386</Options>
+            <ParameterValues membercount=\"2\">
+                <Parameter type=\"FieldReference\">
+                    <FieldReference id=\"6\" name=\"_gInputText1\">
+                        <repetition value=\"1\"></repetition>
+                        <TableOccurrenceReference id=\"1065123\" name=\"_Home\"></TableOccurrenceReference>
+                    </FieldReference>
+                </Parameter>
+                <Parameter type=\"List\">
+                    <List name=\"Unterschrift\" value=\"7\">
+                        <Options type=\"Signature\">
+                            <Parameter type=\"Title\">
+                                <Calculation datatype=\"1\" position=\"1\">
+                                    <Calculation>
+                                        <Text><![CDATA[$Title]]></Text>
+                                        <ChunkList hash=\"09B45D4F131B6CEEEDA418347424D854\">
+                                            <Chunk type=\"VariableReference\">$Title</Chunk>
+                                        </ChunkList>
+                                    </Calculation>
+                                </Calculation>
+                            </Parameter>
+                            <Parameter type=\"Presentation\">
+                                <List name=\"Vollbild\" value=\"0\"></List>
+                            </Parameter>
+                        </Options>
+                    </List>
+                </Parameter>
+            </ParameterValues>
+        </Step>
+        ";
+
+        let expected_output = Some("Von Gerät einfügen [ _Home::_gInputText1 ; Unterschrift ; Title: $Title ; Presentation: Vollbild ]".to_string());
+        assert_eq!(sanitize(xml_input.trim()), expected_output);
+    }
+
+    #[test]
+    fn test_sanitize_configure_machine_learning_model() {
+        let xml_input = "
+        <Step id=\"202\" name=\"Machine Learning-Modell konfigurieren\" enable=\"True\">
+            <ParameterValues membercount=\"1\">
+                <Parameter type=\"operation\">uninstall</Parameter>
+            </ParameterValues>
+        </Step>
+        ";
+
+        let expected_output =
+            Some("Machine Learning-Modell konfigurieren [ Operation: uninstall ]".to_string());
+        assert_eq!(sanitize(xml_input.trim()), expected_output);
+    }
+
+    #[test]
+    fn test_sanitize_perform_javascript_in_web_viewer() {
+        let xml_input = "
+        <Step id=\"175\" name=\"JavaScript in Web Viewer ausführen\" enable=\"True\">
+            <ParameterValues membercount=\"3\">
+                <Parameter type=\"Object\">
+                    <Name>
+                        <Calculation datatype=\"1\" position=\"0\">
+                            <Calculation>
+                                <Text><![CDATA[\"WebViewer\"]]></Text>
+                            </Calculation>
+                        </Calculation>
+                    </Name>
+                </Parameter>
+                <Parameter type=\"FunctionName\">
+                    <Calculation datatype=\"1\" position=\"1\">
+                        <Calculation>
+                            <Text><![CDATA[\"render\"]]></Text>
+                        </Calculation>
+                    </Calculation>
+                </Parameter>
+                <Parameter type=\"Parameters\">
+                    <Calculation datatype=\"1\" position=\"2\">
+                        <Calculation>
+                            <Text><![CDATA[$json]]></Text>
+                        </Calculation>
+                    </Calculation>
+                </Parameter>
+            </ParameterValues>
+        </Step>
+        ";
+
+        let expected_output = Some("JavaScript in Web Viewer ausführen [ Object: \"WebViewer\" ; Function name: \"render\" ; Parameters: $json ]".to_string());
+        assert_eq!(sanitize(xml_input.trim()), expected_output);
+    }
+
+    #[test]
+    fn test_sanitize_boolean_without_type() {
+        let xml_input = "
+        <Step id=\"168\" name=\"Layoutobjekt-Animation festlegen\" enable=\"True\">
+            <ParameterValues membercount=\"1\">
+                <Parameter type=\"Boolean\">
+                    <Boolean id=\"4096\" value=\"False\"></Boolean>
+                </Parameter>
+            </ParameterValues>
+        </Step>
+        ";
+
+        let expected_output = Some("Layoutobjekt-Animation festlegen [ OFF ]".to_string());
+        assert_eq!(sanitize(xml_input.trim()), expected_output);
+    }
+
+    #[test]
+    fn test_sanitize_empty_list() {
+        let xml_input = "
+        <Step id=\"174\" name=\"Touch-Tastatur aktivieren\" enable=\"True\">
+            <ParameterValues membercount=\"1\">
+                <Parameter type=\"List\">
+                    <List name=\"\" value=\"0\"></List>
+                </Parameter>
+            </ParameterValues>
+        </Step>
+        ";
+
+        let expected_output = Some("Touch-Tastatur aktivieren []".to_string());
+        assert_eq!(sanitize(xml_input.trim()), expected_output);
+    }
+
+    #[test]
+    fn test_sanitize_data_source_reference() {
+        let xml_input = "
+        <Step id=\"33\" name=\"Datei öffnen\" enable=\"True\">
+            <ParameterValues membercount=\"2\">
+                <Parameter type=\"Boolean\">
+                    <Boolean type=\"Ausgeblendet öffnen\" id=\"256\" value=\"True\"></Boolean>
+                </Parameter>
+                <Parameter type=\"DataSourceReference\">
+                    <DataSourceReference fileType=\"FMPR\" value=\"1\">
+                        <UniversalPathList fileType=\"FMPR\" membercount=\"1\">
+                            <ObjectList>
+                                <Location><![CDATA[file:Archive.fmp12]]></Location>
+                            </ObjectList>
+                        </UniversalPathList>
+                    </DataSourceReference>
+                </Parameter>
+            </ParameterValues>
+        </Step>
+        ";
+
+        assert_eq!(
+            sanitize_with_coverage(xml_input.trim()),
+            (
+                Some(
+                    "Datei öffnen [ Ausgeblendet öffnen: ON ; File: \"file:Archive.fmp12\" ]"
+                        .to_string()
+                ),
+                true
+            )
+        );
+    }
+
+    #[test]
+    fn test_sanitize_without_parameters() {
+        let xml_input = "
+        <Step id=\"208\" name=\"Sitzungs-ID festlegen\" enable=\"True\">
+        </Step>
+        ";
+
+        let expected_output = Some("Sitzungs-ID festlegen []".to_string());
+        assert_eq!(sanitize(xml_input.trim()), expected_output);
+    }
+
+    #[test]
+    fn test_sanitize_with_coverage_unrendered_parameter() {
+        let xml_input = "
+        <Step id=\"999\" name=\"Future Step\" enable=\"True\">
+            <ParameterValues membercount=\"2\">
+                <Parameter type=\"Boolean\">
+                    <Boolean type=\"With dialog\" id=\"128\" value=\"False\"></Boolean>
+                </Parameter>
+                <Parameter type=\"Model\">
+                    <Model id=\"1\"></Model>
+                </Parameter>
+            </ParameterValues>
+        </Step>
+        ";
+
+        assert_eq!(
+            sanitize_with_coverage(xml_input.trim()),
+            (Some("Future Step [ With dialog: OFF ]".to_string()), false)
+        );
+    }
+
+    #[test]
+    fn test_sanitize_with_coverage_without_parameters() {
+        let xml_input = "
+        <Step id=\"2\" name=\"&lt;unknown&gt;\" enable=\"True\">
+        </Step>
+        ";
+
+        assert_eq!(
+            sanitize_with_coverage(xml_input.trim()),
+            (Some("&lt;unknown&gt; []".to_string()), true)
+        );
+    }
+
+    #[test]
+    fn test_sanitize_with_coverage_empty_parameter_values() {
+        let xml_input = "
+        <Step id=\"34\" name=\"Datei schließen\" enable=\"True\">
+            <ParameterValues membercount=\"0\"></ParameterValues>
+        </Step>
+        ";
+
+        assert_eq!(
+            sanitize_with_coverage(xml_input.trim()),
+            (Some("Datei schließen []".to_string()), true)
+        );
+    }
+
+    #[test]
+    fn test_sanitize_with_coverage_self_closing_data_source_reference() {
+        let xml_input = "
+        <Step id=\"33\" name=\"Datei öffnen\" enable=\"True\">
+            <ParameterValues membercount=\"2\">
+                <Parameter type=\"Boolean\">
+                    <Boolean type=\"Ausgeblendet öffnen\" id=\"256\" value=\"False\"/>
+                </Parameter>
+                <Parameter type=\"DataSourceReference\">
+                    <DataSourceReference id=\"2\" name=\"Archive\"/>
+                </Parameter>
+            </ParameterValues>
+        </Step>
+        ";
+
+        assert_eq!(
+            sanitize_with_coverage(xml_input.trim()),
+            (
+                Some("Datei öffnen [ Ausgeblendet öffnen: OFF ; File: \"Archive\" ]".to_string()),
+                true
+            )
+        );
+    }
+}
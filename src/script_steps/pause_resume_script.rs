@@ -0,0 +1,91 @@
+use crate::calculations::calculation::Calculation;
+use crate::utils::attributes::get_attribute;
+use quick_xml::events::Event;
+use quick_xml::Reader;
+
+pub fn sanitize(step: &str) -> Option<String> {
+    let mut name = String::new();
+    let mut params: Vec<String> = Vec::new();
+
+    let mut reader = Reader::from_str(step);
+    reader.trim_text(true);
+    let mut buf: Vec<u8> = Vec::new();
+    loop {
+        match reader.read_event_into(&mut buf) {
+            Err(_) => continue,
+            Ok(Event::Eof) => break,
+            Ok(Event::Start(e)) => match e.name().as_ref() {
+                b"Step" => name = get_attribute(&e, "name").unwrap().to_string(),
+                b"Options" if get_attribute(&e, "type").is_some() => {
+                    let option = get_attribute(&e, "type").unwrap();
+                    let option = option.trim().trim_end_matches(':');
+                    let duration = Calculation::from_xml(&mut reader, &e).unwrap();
+                    if duration.is_empty() {
+                        params.push(option.to_string());
+                    } else {
+                        params.push(format!("{}: {}", option, duration));
+                    }
+                }
+                _ => {}
+            },
+            _ => {}
+        }
+        buf.clear()
+    }
+
+    if name.is_empty() {
+        None
+    } else if params.is_empty() {
+        Some(format!("{} []", name))
+    } else {
+        Some(format!("{} [ {} ]", name, params.join(" ; ")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sanitize_duration() {
+        let xml_input = "
+        <Step id=\"62\" name=\"Scriptpause setzen\" enable=\"True\">
+            <Options>0</Options>
+            <ParameterValues membercount=\"1\">
+                <Parameter type=\"Options\">
+                    <Options type=\"Dauer (Sekunden): \">
+                        <Calculation datatype=\"1\" position=\"0\">
+                            <Calculation>
+                                <Text><![CDATA[2]]></Text>
+                                <ChunkList hash=\"C81E728D9D4C2F636F067F89CC14862C\">
+                                    <Chunk type=\"NoRef\">2</Chunk>
+                                </ChunkList>
+                            </Calculation>
+                        </Calculation>
+                    </Options>
+                </Parameter>
+            </ParameterValues>
+        </Step>
+        ";
+
+        let expected_output = Some("Scriptpause setzen [ Dauer (Sekunden): 2 ]".to_string());
+        assert_eq!(sanitize(xml_input.trim()), expected_output);
+    }
+
+    #[test]
+    fn test_sanitize_indefinitely() {
+        let xml_input = "
+        <Step id=\"62\" name=\"Scriptpause setzen\" enable=\"True\">
+            <Options>0</Options>
+            <ParameterValues membercount=\"1\">
+                <Parameter type=\"Options\">
+                    <Options type=\"Unbegrenzt\"></Options>
+                </Parameter>
+            </ParameterValues>
+        </Step>
+        ";
+
+        let expected_output = Some("Scriptpause setzen [ Unbegrenzt ]".to_string());
+        assert_eq!(sanitize(xml_input.trim()), expected_output);
+    }
+}
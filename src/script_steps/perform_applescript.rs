@@ -0,0 +1,94 @@
+use crate::calculations::calculation::Calculation;
+use crate::utils::attributes::get_attribute;
+use crate::utils::xml_utils::text_to_string;
+use quick_xml::events::Event;
+use quick_xml::Reader;
+
+pub fn sanitize(step: &str) -> Option<String> {
+    let mut name = String::new();
+    let mut script = String::new();
+    let mut script_type = String::new();
+
+    let mut reader = Reader::from_str(step);
+    reader.trim_text(true);
+    let mut buf: Vec<u8> = Vec::new();
+    loop {
+        match reader.read_event_into(&mut buf) {
+            Err(_) => continue,
+            Ok(Event::Eof) => break,
+            Ok(Event::Start(e)) => match e.name().as_ref() {
+                b"Step" => name = get_attribute(&e, "name").unwrap().to_string(),
+                b"Options" => {
+                    script_type = get_attribute(&e, "type").unwrap_or_default();
+                    if script_type == "Calculation" {
+                        script = Calculation::from_xml(&mut reader, &e).unwrap();
+                    }
+                }
+                _ => {}
+            },
+            Ok(Event::Text(e)) if script_type == "Text" => script = text_to_string(&e),
+            _ => {}
+        }
+        buf.clear()
+    }
+
+    if name.is_empty() {
+        None
+    } else if script.is_empty() {
+        Some(format!("{} []", name))
+    } else {
+        Some(format!("{} [ {}: {} ]", name, script_type, script))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sanitize_native() {
+        let xml_input = "
+        <Step id=\"67\" name=\"AppleScript ausführen\" enable=\"True\">
+            <Options>131078</Options>
+            <ParameterValues membercount=\"1\">
+                <Parameter type=\"Options\">
+                    <Options type=\"Text\">set theName to my name&#13;display dialog theName</Options>
+                </Parameter>
+            </ParameterValues>
+        </Step>
+        ";
+
+        let expected_output = Some(
+            "AppleScript ausführen [ Text: set theName to my name\rdisplay dialog theName ]"
+                .to_string(),
+        );
+        assert_eq!(sanitize(xml_input.trim()), expected_output);
+    }
+
+    #[test]
+    fn test_sanitize_calculated() {
+        let xml_input = "
+        <Step id=\"67\" name=\"AppleScript ausführen\" enable=\"True\">
+            <Options>16386</Options>
+            <ParameterValues membercount=\"1\">
+                <Parameter type=\"Options\">
+                    <Options type=\"Calculation\">
+                        <Calculation datatype=\"1\" position=\"0\">
+                            <Calculation>
+                                <Text><![CDATA[$my.applescript]]></Text>
+                                <ChunkList hash=\"3A4B2B1C9D8E7F6A5B4C3D2E1F0A9B8C\">
+                                    <Chunk type=\"VariableReference\">$my.applescript</Chunk>
+                                </ChunkList>
+                            </Calculation>
+                        </Calculation>
+                    </Options>
+                </Parameter>
+            </ParameterValues>
+        </Step>
+        ";
+
+        let expected_output =
+            Some("AppleScript ausführen [ Calculation: $my.applescript ]".to_string());
+        assert_eq!(sanitize(xml_input.trim()), expected_output);
+    }
+}
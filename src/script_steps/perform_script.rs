@@ -3,12 +3,67 @@ use crate::utils::attributes::get_attribute;
 use quick_xml::events::Event;
 use quick_xml::Reader;
 
+#[derive(Debug, Default)]
+pub struct PerformedScript {
+    reference_type: String,
+    reference_type_id: String,
+    pub id: String,
+    pub reference: String,
+    parameter: String,
+}
+
+impl PerformedScript {
+    /// Scripts called by name are only known at runtime, `reference` holds the calculation.
+    pub fn is_by_name(&self) -> bool {
+        self.reference_type_id == "2"
+    }
+
+    fn display(&self) -> String {
+        let reference = match self.is_by_name() {
+            true => self.reference.to_string(),
+            false => format!("\"{}\"", self.reference),
+        };
+
+        if self.parameter.is_empty() {
+            format!("{} ; {}", self.reference_type, reference)
+        } else {
+            format!(
+                "{} ; {} ; Parameter: {}",
+                self.reference_type, reference, self.parameter
+            )
+        }
+    }
+}
+
+pub fn performed_scripts(step: &str) -> Vec<PerformedScript> {
+    parse(step).1
+}
+
 pub fn sanitize(step: &str) -> Option<String> {
+    let (name, performed_scripts, options) = parse(step);
+
+    let mut params = performed_scripts
+        .iter()
+        .enumerate()
+        .map(|(index, performed_script)| match index {
+            0 => performed_script.display(),
+            _ => format!("Callback: {}", performed_script.display()),
+        })
+        .collect::<Vec<_>>();
+    params.extend(options);
+
+    if name.is_empty() {
+        println!("empty primitive");
+        None
+    } else {
+        Some(format!("{} [ {} ]", name, params.join(" ; ")))
+    }
+}
+
+fn parse(step: &str) -> (String, Vec<PerformedScript>, Vec<String>) {
     let mut name = String::new();
-    let mut script_reference_type = String::new();
-    let mut script_reference_type_id = String::new();
-    let mut script_reference = String::new();
-    let mut calculation = String::new();
+    let mut performed_scripts: Vec<PerformedScript> = Vec::new();
+    let mut options: Vec<String> = Vec::new();
 
     let mut reader = Reader::from_str(step);
     reader.trim_text(true);
@@ -22,20 +77,43 @@ pub fn sanitize(step: &str) -> Option<String> {
                     name = get_attribute(&e, "name").unwrap().to_string();
                 }
                 b"List" => {
-                    script_reference_type_id = get_attribute(&e, "value").unwrap().to_string();
-                    script_reference_type = get_attribute(&e, "name").unwrap().to_string();
-                    if script_reference_type_id.as_str() == "2" {
-                        script_reference = Calculation::from_xml(&mut reader, &e).unwrap();
+                    let mut performed_script = PerformedScript {
+                        reference_type_id: get_attribute(&e, "value").unwrap().to_string(),
+                        reference_type: get_attribute(&e, "name").unwrap().to_string(),
+                        ..Default::default()
+                    };
+                    if performed_script.reference_type_id.as_str() == "2" {
+                        performed_script.reference =
+                            Calculation::from_xml(&mut reader, &e).unwrap();
                     }
+                    performed_scripts.push(performed_script);
                 }
                 b"ScriptReference" => {
-                    script_reference = get_attribute(&e, "name").unwrap().to_string();
+                    if let Some(performed_script) = performed_scripts.last_mut() {
+                        performed_script.id = get_attribute(&e, "id").unwrap_or_default();
+                        performed_script.reference = get_attribute(&e, "name").unwrap().to_string();
+                    }
                 }
                 b"Parameter" => {
                     if get_attribute(&e, "type").unwrap_or("".to_string()).as_str() == "Parameter" {
-                        calculation = Calculation::from_xml(&mut reader, &e).unwrap();
+                        let calculation = Calculation::from_xml(&mut reader, &e).unwrap();
+                        if let Some(performed_script) = performed_scripts.last_mut() {
+                            performed_script.parameter = calculation;
+                        }
                     }
                 }
+                b"Boolean" => options.push(format!(
+                    "{}: {}",
+                    get_attribute(&e, "type").unwrap_or_default(),
+                    match get_attribute(&e, "value").unwrap_or_default().as_str() {
+                        "True" => "ON",
+                        _ => "OFF",
+                    }
+                )),
+                b"State" => options.push(format!(
+                    "State: {}",
+                    get_attribute(&e, "name").unwrap_or_default()
+                )),
                 _ => {}
             },
             _ => {}
@@ -43,24 +121,7 @@ pub fn sanitize(step: &str) -> Option<String> {
         buf.clear()
     }
 
-    if script_reference_type_id.as_str() != "2" {
-        script_reference = format!("\"{}\"", script_reference);
-    }
-
-    if name.is_empty() {
-        println!("empty primitive");
-        None
-    } else if calculation.is_empty() {
-        Some(format!(
-            "{} [ {} ; {} ]",
-            name, script_reference_type, script_reference
-        ))
-    } else {
-        Some(format!(
-            "{} [ {} ; {} ; Parameter: {} ]",
-            name, script_reference_type, script_reference, calculation
-        ))
-    }
+    (name, performed_scripts, options)
 }
 
 #[cfg(test)]
@@ -163,4 +224,38 @@ mod tests {
             Some("Script ausführen [ Nach Name ; \"Do something\" ; Parameter: 123 ]".to_string());
         assert_eq!(sanitize(xml_input.trim()), expected_output);
     }
+
+    #[test]
+    fn test_perform_script_on_server() {
+        let xml_input = "
+		<Step id=\"164\" name=\"Script auf Server ausführen\" enable=\"True\">
+			<Options>64</Options>
+			<ParameterValues membercount=\"3\">
+				<Parameter type=\"List\">
+					<List name=\"Aus Liste\" value=\"1\">
+						<ScriptReference id=\"11\" name=\"Server Job\"></ScriptReference>
+					</List>
+				</Parameter>
+				<Parameter type=\"Parameter\">
+					<Parameter>
+						<Calculation datatype=\"1\" position=\"0\">
+							<Calculation>
+								<Text><![CDATA[$json]]></Text>
+								<ChunkList hash=\"2C35E2E0E1A1A0E2C5A62C7C3E1D4E4A\">
+									<Chunk type=\"VariableReference\">$json</Chunk>
+								</ChunkList>
+							</Calculation>
+						</Calculation>
+					</Parameter>
+				</Parameter>
+				<Parameter type=\"Boolean\">
+					<Boolean type=\"Auf Beendigung warten\" id=\"256\" value=\"True\"></Boolean>
+				</Parameter>
+			</ParameterValues>
+		</Step>
+        ";
+
+        let expected_output = Some("Script auf Server ausführen [ Aus Liste ; \"Server Job\" ; Parameter: $json ; Auf Beendigung warten: ON ]".to_string());
+        assert_eq!(sanitize(xml_input.trim()), expected_output);
+    }
 }
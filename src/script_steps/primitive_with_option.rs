@@ -0,0 +1,90 @@
+use crate::utils::attributes::get_attribute;
+use quick_xml::events::Event;
+use quick_xml::Reader;
+
+pub fn sanitize(step: &str) -> Option<String> {
+    let mut name = String::new();
+    let mut params: Vec<String> = Vec::new();
+
+    let mut reader = Reader::from_str(step);
+    reader.trim_text(true);
+    let mut buf: Vec<u8> = Vec::new();
+    loop {
+        match reader.read_event_into(&mut buf) {
+            Err(_) => continue,
+            Ok(Event::Eof) => break,
+            Ok(Event::Start(e)) => match e.name().as_ref() {
+                b"Step" => name = get_attribute(&e, "name").unwrap().to_string(),
+                b"List" => {
+                    params.push(get_attribute(&e, "name").unwrap_or_default());
+                }
+                b"Boolean" => {
+                    params.push(format!(
+                        "{}: {}",
+                        get_attribute(&e, "type").unwrap_or_default().trim(),
+                        match get_attribute(&e, "value").unwrap_or_default().as_str() {
+                            "True" => "ON",
+                            _ => "OFF",
+                        }
+                    ));
+                }
+                _ => {}
+            },
+            _ => {}
+        }
+        buf.clear()
+    }
+
+    if name.is_empty() {
+        None
+    } else if params.is_empty() {
+        Some(format!("{} []", name))
+    } else {
+        Some(format!("{} [ {} ]", name, params.join(" ; ")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sanitize_view_as() {
+        let xml_input = "
+        <Step id=\"30\" name=\"Datensätze zeigen als\" enable=\"True\">
+            <Options>2</Options>
+            <ParameterValues membercount=\"1\">
+                <Parameter type=\"List\">
+                    <List name=\"Als Formular anzeigen\" value=\"1\"></List>
+                </Parameter>
+            </ParameterValues>
+        </Step>
+        ";
+
+        let expected_output = Some("Datensätze zeigen als [ Als Formular anzeigen ]".to_string());
+        assert_eq!(sanitize(xml_input.trim()), expected_output);
+    }
+
+    #[test]
+    fn test_sanitize_show_hide_toolbars() {
+        let xml_input = "
+        <Step id=\"29\" name=\"Werkzeugleisten ein-/ausblenden\" enable=\"True\">
+            <Options>2</Options>
+            <ParameterValues membercount=\"3\">
+                <Parameter type=\"Boolean\">
+                    <Boolean type=\"Fixieren\" id=\"524288\" value=\"True\"></Boolean>
+                </Parameter>
+                <Parameter type=\"Boolean\">
+                    <Boolean type=\"Mit Datensatz-bearbeiten-Werkzeugleiste \" id=\"256\" value=\"False\"></Boolean>
+                </Parameter>
+                <Parameter type=\"List\">
+                    <List name=\"Umschalten\" value=\"3\"></List>
+                </Parameter>
+            </ParameterValues>
+        </Step>
+        ";
+
+        let expected_output = Some("Werkzeugleisten ein-/ausblenden [ Fixieren: ON ; Mit Datensatz-bearbeiten-Werkzeugleiste: OFF ; Umschalten ]".to_string());
+        assert_eq!(sanitize(xml_input.trim()), expected_output);
+    }
+}
@@ -0,0 +1,127 @@
+use crate::calculations::calculation::Calculation;
+use crate::utils::attributes::get_attribute;
+use quick_xml::events::Event;
+use quick_xml::Reader;
+
+pub fn sanitize(step: &str) -> Option<String> {
+    let mut name = String::new();
+    let mut params: Vec<String> = Vec::new();
+    let mut calculations: Vec<(String, String)> = Vec::new();
+
+    let mut reader = Reader::from_str(step);
+    reader.trim_text(true);
+    let mut buf: Vec<u8> = Vec::new();
+    loop {
+        match reader.read_event_into(&mut buf) {
+            Err(_) => continue,
+            Ok(Event::Eof) => break,
+            Ok(Event::Start(e)) => match e.name().as_ref() {
+                b"Step" => name = get_attribute(&e, "name").unwrap().to_string(),
+                b"Boolean" => {
+                    params.push(format!(
+                        "{}: {}",
+                        get_attribute(&e, "type").unwrap_or_default(),
+                        match get_attribute(&e, "value").unwrap_or_default().as_str() {
+                            "True" => "ON",
+                            _ => "OFF",
+                        }
+                    ));
+                }
+                b"Parameter" => match get_attribute(&e, "type").unwrap_or_default().as_str() {
+                    "Boolean" => {}
+                    parameter_type => calculations.push((
+                        parameter_type.to_string(),
+                        Calculation::from_xml(&mut reader, &e).unwrap(),
+                    )),
+                },
+                _ => {}
+            },
+            _ => {}
+        }
+        buf.clear()
+    }
+
+    for (parameter_type, calculation) in calculations {
+        let label = match parameter_type.as_str() {
+            "ErrorCode" => "Error code".to_string(),
+            "ErrorMessage" => "Error message".to_string(),
+            _ => parameter_type,
+        };
+        if !calculation.is_empty() {
+            params.push(format!("{}: {}", label, calculation));
+        }
+    }
+
+    if name.is_empty() {
+        None
+    } else if params.is_empty() {
+        Some(format!("{} []", name))
+    } else {
+        Some(format!("{} [ {} ]", name, params.join(" ; ")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sanitize_without_condition() {
+        let xml_input = "
+        <Step id=\"207\" name=\"Transaktion rückgängig\" enable=\"True\">
+            <ParameterValues membercount=\"2\">
+                <Parameter type=\"Boolean\">
+                    <Boolean type=\"Bedingung\" id=\"256\" value=\"False\"></Boolean>
+                </Parameter>
+                <Parameter type=\"Boolean\">
+                    <Boolean type=\"Fehlercode\" id=\"512\" value=\"False\"></Boolean>
+                </Parameter>
+            </ParameterValues>
+        </Step>
+        ";
+
+        let expected_output =
+            Some("Transaktion rückgängig [ Bedingung: OFF ; Fehlercode: OFF ]".to_string());
+        assert_eq!(sanitize(xml_input.trim()), expected_output);
+    }
+
+    #[test]
+    fn test_sanitize_with_condition_and_error_code() {
+        let xml_input = "
+        <Step id=\"207\" name=\"Transaktion rückgängig\" enable=\"True\">
+            <ParameterValues membercount=\"5\">
+                <Parameter type=\"Boolean\">
+                    <Boolean type=\"Bedingung\" id=\"256\" value=\"True\"></Boolean>
+                </Parameter>
+                <Parameter type=\"Boolean\">
+                    <Boolean type=\"Fehlercode\" id=\"512\" value=\"True\"></Boolean>
+                </Parameter>
+                <Parameter type=\"Condition\">
+                    <Calculation datatype=\"1\" position=\"0\">
+                        <Calculation>
+                            <Text><![CDATA[Get ( LastError ) ≠ 0]]></Text>
+                        </Calculation>
+                    </Calculation>
+                </Parameter>
+                <Parameter type=\"ErrorCode\">
+                    <Calculation datatype=\"1\" position=\"1\">
+                        <Calculation>
+                            <Text><![CDATA[5000]]></Text>
+                        </Calculation>
+                    </Calculation>
+                </Parameter>
+                <Parameter type=\"ErrorMessage\">
+                    <Calculation datatype=\"1\" position=\"2\">
+                        <Calculation>
+                            <Text><![CDATA[\"Import failed\"]]></Text>
+                        </Calculation>
+                    </Calculation>
+                </Parameter>
+            </ParameterValues>
+        </Step>
+        ";
+
+        let expected_output = Some("Transaktion rückgängig [ Bedingung: ON ; Fehlercode: ON ; Condition: Get ( LastError ) ≠ 0 ; Error code: 5000 ; Error message: \"Import failed\" ]".to_string());
+        assert_eq!(sanitize(xml_input.trim()), expected_output);
+    }
+}
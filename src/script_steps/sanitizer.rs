@@ -1,10 +1,28 @@
+use crate::calculations::calculation::CalculationFormat;
 use crate::script_steps;
-use crate::script_steps::constants::{id_to_script_step, ScriptStep};
+use crate::script_steps::constants::{id_to_script_step, step_name, ScriptStep, StepLanguage};
+use crate::Settings;
 
-pub fn sanitize(step_id: &str, step_xml: &str) -> Option<String> {
+pub fn sanitize(step_id: &str, step_xml: &str, settings: &Settings) -> Option<String> {
     let is_enabled = script_steps::is_enabled::sanitize(step_xml);
 
-    let step_sanitized = match id_to_script_step(step_id) {
+    let step_sanitized =
+        CalculationFormat::new(settings).apply(|| sanitize_step(step_id, step_xml, settings));
+
+    match step_sanitized.map(|step| localize(step_id, step_xml, step, settings.step_language)) {
+        None => {
+            println!("Could not parse: {}", step_xml);
+            None
+        }
+        Some(step) => match is_enabled {
+            true => Some(step),
+            false => Some(format!("// {}", step)),
+        },
+    }
+}
+
+fn sanitize_step(step_id: &str, step_xml: &str, settings: &Settings) -> Option<String> {
+    match id_to_script_step(step_id) {
         ScriptStep::PerformScript => script_steps::perform_script::sanitize(step_xml),
         ScriptStep::GoToPreviousField => script_steps::primitive::sanitize(step_xml),
         ScriptStep::GoToNextField => script_steps::primitive::sanitize(step_xml),
@@ -13,6 +31,10 @@ pub fn sanitize(step_id: &str, step_xml: &str) -> Option<String> {
         ScriptStep::DuplicateRecordRequest => script_steps::primitive::sanitize(step_xml),
         ScriptStep::DeleteRecordRequest => script_steps::primitive_with_boolean::sanitize(step_xml),
         ScriptStep::DeleteAllRecords => script_steps::primitive_with_boolean::sanitize(step_xml),
+        ScriptStep::InsertFromIndex => script_steps::insert_value::sanitize(step_xml),
+        ScriptStep::InsertFromLastVisited => script_steps::insert_value::sanitize(step_xml),
+        ScriptStep::InsertCurrentDate => script_steps::insert_value::sanitize(step_xml),
+        ScriptStep::InsertCurrentTime => script_steps::insert_value::sanitize(step_xml),
         ScriptStep::GoToRecordRequestPage => script_steps::go_to_record::sanitize(step_xml),
         ScriptStep::GoToField => script_steps::go_to_field::sanitize(step_xml),
         ScriptStep::CheckRecord => script_steps::primitive::sanitize(step_xml),
@@ -25,31 +47,56 @@ pub fn sanitize(step_id: &str, step_xml: &str) -> Option<String> {
         ScriptStep::OmitMultipleRecords => script_steps::omit_multiple_records::sanitize(step_xml),
         ScriptStep::ShowOmittedOnly => script_steps::primitive::sanitize(step_xml),
         ScriptStep::PerformFind => script_steps::perform_find::sanitize(step_xml),
+        ScriptStep::ShowHideToolbars => script_steps::primitive_with_option::sanitize(step_xml),
+        ScriptStep::ViewAs => script_steps::primitive_with_option::sanitize(step_xml),
+        ScriptStep::AdjustWindow => script_steps::primitive_with_option::sanitize(step_xml),
         ScriptStep::OpenHelp => script_steps::primitive::sanitize(step_xml),
+        ScriptStep::ImportRecords => script_steps::import_records::sanitize(step_xml),
+        ScriptStep::ExportRecords => script_steps::export_records::sanitize(step_xml),
         ScriptStep::OpenManageDatabase => script_steps::primitive::sanitize(step_xml),
+        ScriptStep::SortRecords => script_steps::sort_records::sanitize(step_xml),
         ScriptStep::ExitApplication => script_steps::primitive::sanitize(step_xml),
         ScriptStep::SelectAll => script_steps::primitive::sanitize(step_xml),
         ScriptStep::EnterBrowseMode => script_steps::primitive::sanitize(step_xml),
+        ScriptStep::InsertPicture => script_steps::insert_value::sanitize(step_xml),
+        ScriptStep::SendEvent => script_steps::send_event::sanitize(step_xml),
+        ScriptStep::InsertCurrentUserName => script_steps::insert_value::sanitize(step_xml),
+        ScriptStep::InsertText => script_steps::insert_value::sanitize(step_xml),
+        ScriptStep::PauseResumeScript => script_steps::pause_resume_script::sanitize(step_xml),
+        ScriptStep::SendMail => script_steps::send_mail::sanitize(step_xml),
+        ScriptStep::SendDdeExecute => script_steps::parameter_list::sanitize(step_xml),
+        ScriptStep::DialPhone => script_steps::insert_value::sanitize(step_xml),
+        ScriptStep::PerformAppleScript => script_steps::perform_applescript::sanitize(step_xml),
         ScriptStep::IfStart => script_steps::if_start::sanitize(step_xml),
         ScriptStep::Else => script_steps::primitive::sanitize(step_xml),
         ScriptStep::IfEnd => script_steps::primitive::sanitize(step_xml),
         ScriptStep::LoopStart => script_steps::primitive::sanitize(step_xml),
         ScriptStep::ExitLoopIf => script_steps::if_start::sanitize(step_xml),
         ScriptStep::LoopEnd => script_steps::primitive::sanitize(step_xml),
+        ScriptStep::GoToRelatedRecord => script_steps::go_to_related_record::sanitize(step_xml),
         ScriptStep::CommitRecordRequests => script_steps::commit::sanitize(step_xml),
         ScriptStep::SetFieldData => script_steps::set_field_data::sanitize(step_xml),
+        ScriptStep::InsertCalculatedResult => script_steps::insert_value::sanitize(step_xml),
         ScriptStep::FixWindow => script_steps::primitive::sanitize(step_xml),
+        ScriptStep::RefreshWindow => script_steps::primitive_with_option::sanitize(step_xml),
+        ScriptStep::ScrollWindow => script_steps::primitive_with_option::sanitize(step_xml),
         ScriptStep::NewFile => script_steps::primitive::sanitize(step_xml),
+        ScriptStep::ChangePassword => {
+            script_steps::account::sanitize(step_xml, settings.show_passwords)
+        }
         ScriptStep::AllowUserAbort => script_steps::primitive_with_boolean::sanitize(step_xml),
         ScriptStep::SetErrorRecording => script_steps::primitive_with_boolean::sanitize(step_xml),
+        ScriptStep::ShowCustomDialog => script_steps::show_custom_dialog::sanitize(step_xml),
         ScriptStep::OpenScriptWorkspace => script_steps::primitive::sanitize(step_xml),
         ScriptStep::Comment => script_steps::comment::sanitize(step_xml),
         ScriptStep::HaltScript => script_steps::primitive::sanitize(step_xml),
         ScriptStep::ReplaceFieldContents => {
             script_steps::replace_field_contents::sanitize(step_xml)
         }
+        ScriptStep::ShowHideTextRuler => script_steps::primitive_with_option::sanitize(step_xml),
         ScriptStep::Beep => script_steps::primitive::sanitize(step_xml),
         ScriptStep::SetUseSystemFormats => script_steps::primitive_with_boolean::sanitize(step_xml),
+        ScriptStep::SetZoomLevel => script_steps::primitive_with_option::sanitize(step_xml),
         ScriptStep::GoToPortalRow => script_steps::go_to_portal_row::sanitize(step_xml),
         ScriptStep::CopyRecordRequest => script_steps::primitive::sanitize(step_xml),
         ScriptStep::FlushCacheToDisk => script_steps::primitive::sanitize(step_xml),
@@ -58,44 +105,248 @@ pub fn sanitize(step_id: &str, step_xml: &str) -> Option<String> {
         ScriptStep::CorrectWord => script_steps::primitive::sanitize(step_xml),
         ScriptStep::SpellingOptions => script_steps::primitive::sanitize(step_xml),
         ScriptStep::SelectDictionaries => script_steps::primitive::sanitize(step_xml),
+        ScriptStep::OpenUrl => script_steps::insert_from_url::sanitize(step_xml),
         ScriptStep::OpenManageValueLists => script_steps::primitive::sanitize(step_xml),
         ScriptStep::OpenSharing => script_steps::primitive::sanitize(step_xml),
         ScriptStep::OpenFileOptions => script_steps::primitive::sanitize(step_xml),
         ScriptStep::AllowFormattingBar => script_steps::primitive_with_boolean::sanitize(step_xml),
+        ScriptStep::ExecuteSql => script_steps::execute_sql::sanitize(step_xml),
         ScriptStep::OpenHosts => script_steps::primitive::sanitize(step_xml),
         ScriptStep::EditUserDictionary => script_steps::primitive::sanitize(step_xml),
+        ScriptStep::MoveResizeWindow => script_steps::move_resize_window::sanitize(step_xml),
+        ScriptStep::ArrangeAllWindows => script_steps::primitive_with_option::sanitize(step_xml),
         ScriptStep::CloseWindow => script_steps::close_window::sanitize(step_xml),
         ScriptStep::NewWindow => script_steps::new_window::sanitize(step_xml),
+        ScriptStep::SelectWindow => script_steps::select_window::sanitize(step_xml),
+        ScriptStep::SetWindowTitle => script_steps::set_window_title::sanitize(step_xml),
         ScriptStep::IfElse => script_steps::if_start::sanitize(step_xml),
         ScriptStep::ConstrainFoundSet => script_steps::perform_find::sanitize(step_xml),
         ScriptStep::ExtendFoundSet => script_steps::perform_find::sanitize(step_xml),
         ScriptStep::OpenFindReplace => script_steps::primitive::sanitize(step_xml),
+        ScriptStep::InsertFile => script_steps::insert_value::sanitize(step_xml),
+        ScriptStep::AddAccount => {
+            script_steps::account::sanitize(step_xml, settings.show_passwords)
+        }
+        ScriptStep::DeleteAccount => {
+            script_steps::account::sanitize(step_xml, settings.show_passwords)
+        }
+        ScriptStep::ResetAccountPassword => {
+            script_steps::account::sanitize(step_xml, settings.show_passwords)
+        }
+        ScriptStep::EnableAccount => {
+            script_steps::account::sanitize(step_xml, settings.show_passwords)
+        }
+        ScriptStep::ReLogin => script_steps::account::sanitize(step_xml, settings.show_passwords),
         ScriptStep::OpenManageDataSources => script_steps::primitive::sanitize(step_xml),
         ScriptStep::SetVariable => script_steps::set_variable::sanitize(step_xml),
+        ScriptStep::SaveRecordsAsExcel => script_steps::save_records_as::sanitize(step_xml),
+        ScriptStep::SaveRecordsAsPdf => script_steps::save_records_as::sanitize(step_xml),
         ScriptStep::GoToObject => script_steps::go_to_object::sanitize(step_xml),
+        ScriptStep::SetFieldByName => script_steps::set_field_by_name::sanitize(step_xml),
         ScriptStep::OpenEditSavedFinds => script_steps::primitive::sanitize(step_xml),
         ScriptStep::OpenManageLayouts => script_steps::primitive::sanitize(step_xml),
+        ScriptStep::SortRecordsByField => script_steps::field_with_option::sanitize(step_xml),
+        ScriptStep::FindMatchingRecords => script_steps::field_with_option::sanitize(step_xml),
         ScriptStep::OpenManageContainers => script_steps::primitive::sanitize(step_xml),
+        ScriptStep::InsertPdf => script_steps::insert_value::sanitize(step_xml),
+        ScriptStep::InsertAudioVideo => script_steps::insert_value::sanitize(step_xml),
+        ScriptStep::InsertFromUrl => script_steps::insert_from_url::sanitize(step_xml),
+        ScriptStep::InsertFromDevice => script_steps::parameter_list::sanitize(step_xml),
+        ScriptStep::PerformScriptOnServer => script_steps::perform_script::sanitize(step_xml),
         ScriptStep::OpenManageThemes => script_steps::primitive::sanitize(step_xml),
+        ScriptStep::ShowHideMenubar => script_steps::primitive_with_option::sanitize(step_xml),
         ScriptStep::RefreshObject => script_steps::refresh_object::sanitize(step_xml),
         ScriptStep::ClosePopover => script_steps::primitive::sanitize(step_xml),
         ScriptStep::UploadToServer => script_steps::primitive::sanitize(step_xml),
+        ScriptStep::EnableTouchKeyboard => script_steps::parameter_list::sanitize(step_xml),
+        ScriptStep::PerformJavaScriptInWebViewer => {
+            script_steps::parameter_list::sanitize(step_xml)
+        }
+        ScriptStep::SetAllowedOrientations => script_steps::parameter_list::sanitize(step_xml),
+        ScriptStep::AvPlayerPlay => script_steps::parameter_list::sanitize(step_xml),
+        ScriptStep::AvPlayerSetPlaybackState => script_steps::parameter_list::sanitize(step_xml),
+        ScriptStep::AvPlayerSetOptions => script_steps::parameter_list::sanitize(step_xml),
+        ScriptStep::RefreshPortal => script_steps::parameter_list::sanitize(step_xml),
+        ScriptStep::GetFolderPath => script_steps::parameter_list::sanitize(step_xml),
+        ScriptStep::TruncateTable => script_steps::field_with_option::sanitize(step_xml),
         ScriptStep::OpenMyApps => script_steps::primitive::sanitize(step_xml),
-        ScriptStep::Unknown => Option::from(format!(
-            "{} ⚠️⚠️⚠️ FM-XML-EXPORT-EXPLODER: UNKNOWN SCRIPT-STEP [ ID: {:?} ] ⚠️⚠️⚠️",
-            script_steps::primitive::sanitize(step_xml).unwrap(),
-            step_id
-        )),
+        ScriptStep::ConfigureRegionMonitorScript => {
+            script_steps::parameter_list::sanitize(step_xml)
+        }
+        ScriptStep::ConfigureLocalNotification => script_steps::parameter_list::sanitize(step_xml),
+        ScriptStep::GetFileExists => script_steps::data_file::sanitize(step_xml),
+        ScriptStep::GetFileSize => script_steps::data_file::sanitize(step_xml),
+        ScriptStep::CreateDataFile => script_steps::data_file::sanitize(step_xml),
+        ScriptStep::OpenDataFile => script_steps::data_file::sanitize(step_xml),
+        ScriptStep::WriteToDataFile => script_steps::data_file::sanitize(step_xml),
+        ScriptStep::ReadFromDataFile => script_steps::data_file::sanitize(step_xml),
+        ScriptStep::GetDataFilePosition => script_steps::data_file::sanitize(step_xml),
+        ScriptStep::SetDataFilePosition => script_steps::data_file::sanitize(step_xml),
+        ScriptStep::CloseDataFile => script_steps::data_file::sanitize(step_xml),
+        ScriptStep::DeleteFile => script_steps::data_file::sanitize(step_xml),
+        ScriptStep::RenameFile => script_steps::data_file::sanitize(step_xml),
+        ScriptStep::SetErrorLogging => script_steps::parameter_list::sanitize(step_xml),
+        ScriptStep::ConfigureNfcReading => script_steps::parameter_list::sanitize(step_xml),
+        ScriptStep::ConfigureMachineLearningModel => {
+            script_steps::parameter_list::sanitize(step_xml)
+        }
+        ScriptStep::ExecuteFileMakerDataApi => script_steps::parameter_list::sanitize(step_xml),
+        ScriptStep::OpenTransaction => script_steps::primitive_with_option::sanitize(step_xml),
+        ScriptStep::CommitTransaction => script_steps::primitive::sanitize(step_xml),
+        ScriptStep::RevertTransaction => script_steps::revert_transaction::sanitize(step_xml),
+        ScriptStep::SetSessionIdentifier => script_steps::parameter_list::sanitize(step_xml),
+        ScriptStep::SetDictionary => script_steps::parameter_list::sanitize(step_xml),
+        ScriptStep::PerformScriptOnServerWithCallback => {
+            script_steps::perform_script::sanitize(step_xml)
+        }
+        ScriptStep::TriggerClarisConnectFlow => script_steps::parameter_list::sanitize(step_xml),
+        ScriptStep::ConfigureAiAccount => script_steps::parameter_list::sanitize(step_xml),
+        ScriptStep::InsertEmbedding => script_steps::parameter_list::sanitize(step_xml),
+        ScriptStep::InsertEmbeddingInFoundSet => script_steps::parameter_list::sanitize(step_xml),
+        ScriptStep::PerformSemanticFind => script_steps::parameter_list::sanitize(step_xml),
+        ScriptStep::Unknown => match script_steps::parameter_list::sanitize_with_coverage(step_xml)
+        {
+            (Some(step), true) => Some(step),
+            (step, _) => Option::from(format!(
+                "{} ⚠️⚠️⚠️ FM-XML-EXPORT-EXPLODER: UNKNOWN SCRIPT-STEP [ ID: {:?} ] ⚠️⚠️⚠️",
+                step.unwrap_or_default(),
+                step_id
+            )),
+        },
+    }
+}
+
+fn localize(step_id: &str, step_xml: &str, step: String, language: StepLanguage) -> String {
+    let source_name = match script_steps::primitive::sanitize(step_xml) {
+        Some(primitive) => primitive.trim_end_matches(" []").to_string(),
+        None => return step,
     };
 
-    match step_sanitized {
-        None => {
-            println!("Could not parse: {}", step_xml);
-            None
+    match step.strip_prefix(&source_name) {
+        Some(rest) if !source_name.is_empty() => {
+            format!("{}{}", step_name(step_id, &source_name, language), rest)
         }
-        Some(step) => match is_enabled {
-            true => Some(step),
-            false => Some(format!("// {}", step)),
-        },
+        _ => step,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calculations::function_names::FunctionLanguage;
+
+    #[test]
+    fn test_sanitize_step_language() {
+        let xml_input = "
+        <Step id=\"86\" name=\"Fehleraufzeichnung setzen\" enable=\"True\">
+            <Options>0</Options>
+            <ParameterValues membercount=\"1\">
+                <Parameter type=\"Boolean\">
+                    <Boolean type=\"Ein/Aus\" id=\"100\" value=\"True\"></Boolean>
+                </Parameter>
+            </ParameterValues>
+        </Step>
+        ";
+
+        let mut settings = Settings::default();
+        assert_eq!(
+            sanitize("86", xml_input.trim(), &settings),
+            Some("Fehleraufzeichnung setzen [ Ein/Aus: ON ]".to_string())
+        );
+
+        settings.step_language = StepLanguage::En;
+        assert_eq!(
+            sanitize("86", xml_input.trim(), &settings),
+            Some("Set Error Capture [ Ein/Aus: ON ]".to_string())
+        );
+
+        settings.step_language = StepLanguage::De;
+        assert_eq!(
+            sanitize("86", xml_input.trim(), &settings),
+            Some("Fehleraufzeichnung setzen [ Ein/Aus: ON ]".to_string())
+        );
+    }
+
+    #[test]
+    fn test_sanitize_step_language_disabled_comment() {
+        let xml_input = "
+        <Step id=\"89\" name=\"# (Kommentar)\" enable=\"False\">
+            <Options>0</Options>
+            <ParameterValues membercount=\"1\">
+                <Parameter type=\"Comment\">
+                    <Comment value=\"Kommentar\"></Comment>
+                </Parameter>
+            </ParameterValues>
+        </Step>
+        ";
+
+        let settings = Settings {
+            step_language: StepLanguage::En,
+            ..Default::default()
+        };
+        assert_eq!(
+            sanitize("89", xml_input.trim(), &settings),
+            Some("// # Kommentar".to_string())
+        );
+    }
+
+    #[test]
+    fn test_sanitize_pretty_print_calculations() {
+        let xml_input = "
+        <Step id=\"141\" name=\"Variable setzen\" enable=\"True\">
+            <Options>16388</Options>
+            <ParameterValues membercount=\"1\">
+                <Parameter type=\"Variable\">
+                    <value>
+                        <Calculation datatype=\"1\" position=\"1\">
+                            <Calculation>
+                                <Text><![CDATA[Wenn ( IstLeer ( $a ) ; 0 ; $a )]]></Text>
+                                <ChunkList hash=\"00000000000000000000000000000000\">
+                                    <Chunk type=\"FunctionRef\">Wenn</Chunk>
+                                    <Chunk type=\"NoRef\"> ( </Chunk>
+                                    <Chunk type=\"FunctionRef\">IstLeer</Chunk>
+                                    <Chunk type=\"NoRef\"> ( </Chunk>
+                                    <Chunk type=\"VariableReference\">$a</Chunk>
+                                    <Chunk type=\"NoRef\"> ) ; 0 ; </Chunk>
+                                    <Chunk type=\"VariableReference\">$a</Chunk>
+                                    <Chunk type=\"NoRef\"> )</Chunk>
+                                </ChunkList>
+                            </Calculation>
+                        </Calculation>
+                    </value>
+                    <Name value=\"$result\"></Name>
+                </Parameter>
+            </ParameterValues>
+        </Step>
+        ";
+
+        let mut settings = Settings::default();
+        assert_eq!(
+            sanitize("141", xml_input.trim(), &settings),
+            Some("Variable setzen [ $result ; Wenn ( IstLeer ( $a ) ; 0 ; $a ) ]".to_string())
+        );
+
+        settings.pretty_print_calculations = true;
+        assert_eq!(
+            sanitize("141", xml_input.trim(), &settings),
+            Some(
+                "Variable setzen [ $result ; Wenn (\r\tIstLeer ( $a ) ;\r\t0 ;\r\t$a\r) ]"
+                    .to_string()
+            )
+        );
+
+        settings.step_language = StepLanguage::En;
+        assert_eq!(
+            sanitize("141", xml_input.trim(), &settings),
+            Some(
+                "Set Variable [ $result ; Wenn (\r\tIstLeer ( $a ) ;\r\t0 ;\r\t$a\r) ]".to_string()
+            )
+        );
+
+        settings.function_language = FunctionLanguage::En;
+        assert_eq!(
+            sanitize("141", xml_input.trim(), &settings),
+            Some("Set Variable [ $result ; If (\r\tIsEmpty ( $a ) ;\r\t0 ;\r\t$a\r) ]".to_string())
+        );
     }
 }
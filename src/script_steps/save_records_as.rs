@@ -0,0 +1,205 @@
+use crate::calculations::calculation::Calculation;
+use crate::calculations::path_list::UniversalPathList;
+use crate::utils::attributes::get_attribute;
+use quick_xml::events::Event;
+use quick_xml::Reader;
+
+pub fn sanitize(step: &str) -> Option<String> {
+    let mut name = String::new();
+    let mut params: Vec<String> = Vec::new();
+    let mut restore = false;
+    let mut path_list = UniversalPathList::default();
+    let mut records = String::new();
+    let mut use_field_names = false;
+    let mut document_options: Vec<String> = Vec::new();
+
+    let mut reader = Reader::from_str(step);
+    reader.trim_text(true);
+    let mut buf: Vec<u8> = Vec::new();
+    loop {
+        match reader.read_event_into(&mut buf) {
+            Err(_) => continue,
+            Ok(Event::Eof) => break,
+            Ok(Event::Start(e)) => match e.name().as_ref() {
+                b"Step" => name = get_attribute(&e, "name").unwrap().to_string(),
+                b"Boolean" => {
+                    params.push(format!(
+                        "{}: {}",
+                        get_attribute(&e, "type").unwrap_or_default(),
+                        match get_attribute(&e, "value").unwrap_or_default().as_str() {
+                            "True" => "ON",
+                            _ => "OFF",
+                        }
+                    ));
+                }
+                b"Restore" => restore = get_attribute(&e, "value").unwrap_or_default() == "True",
+                b"UniversalPathList" => {
+                    path_list = UniversalPathList::from_xml(&mut reader, &e).unwrap();
+                }
+                b"Options" => {
+                    if let Some(value) = get_attribute(&e, "type") {
+                        records = value;
+                    }
+                }
+                b"Save" => {
+                    records = get_attribute(&e, "type").unwrap_or_default();
+                    use_field_names =
+                        get_attribute(&e, "useFieldNames").unwrap_or_default() == "True";
+                }
+                b"Parameter" => {
+                    let parameter_type = get_attribute(&e, "type").unwrap_or_default();
+                    if let "Worksheet" | "Title" | "Subject" | "Author" = parameter_type.as_str() {
+                        document_options.push(format!(
+                            "{}: {}",
+                            parameter_type,
+                            Calculation::from_xml(&mut reader, &e).unwrap()
+                        ));
+                    }
+                }
+                _ => {}
+            },
+            _ => {}
+        }
+        buf.clear()
+    }
+
+    if restore {
+        params.insert(0, "Restore".to_string());
+    }
+    if !records.is_empty() {
+        params.push(format!("Records: {}", records));
+    }
+    if use_field_names {
+        params.push("Use field names as column names".to_string());
+    }
+    params.extend(document_options);
+    if !path_list.paths.is_empty() {
+        params.push(format!("Path: {}", path_list.display().unwrap()));
+    }
+    if path_list.auto_open {
+        params.push("Automatically open".to_string());
+    }
+    if path_list.create_mail {
+        params.push("Create email".to_string());
+    }
+
+    if name.is_empty() {
+        None
+    } else {
+        Some(format!("{} [ {} ]", name, params.join(" ; ")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sanitize_pdf() {
+        let xml_input = "
+        <Step id=\"144\" name=\"Datensätze als PDF speichern\" enable=\"True\">
+            <Options>1644183968</Options>
+            <ParameterValues membercount=\"6\">
+                <Parameter type=\"Restore\">
+                    <Restore value=\"True\"></Restore>
+                </Parameter>
+                <Parameter type=\"Boolean\">
+                    <Boolean type=\"An vorhandene PDF anhängen\" id=\"256\" value=\"True\"></Boolean>
+                </Parameter>
+                <Parameter type=\"Boolean\">
+                    <Boolean type=\"Mit Dialog\" id=\"128\" value=\"False\"></Boolean>
+                </Parameter>
+                <Parameter type=\"UniversalPathList\">
+                    <UniversalPathList AutoOpen=\"True\" CreateMail=\"False\" membercount=\"1\">
+                        <ObjectList>
+                            <Location><![CDATA[$Path]]></Location>
+                        </ObjectList>
+                    </UniversalPathList>
+                </Parameter>
+                <Parameter type=\"Options\">
+                    <Options type=\"Alle aufgerufenen Datensätze\" value=\"1\">
+                        <Document></Document>
+                        <Pages>
+                            <Parameter type=\"from\">
+                                <Calculation datatype=\"1\" position=\"4\">
+                                    <Calculation>
+                                        <Text><![CDATA[1]]></Text>
+                                        <ChunkList hash=\"A93F17BC54CA1958073B692697D5ED21\">
+                                            <Chunk type=\"NoRef\">1</Chunk>
+                                        </ChunkList>
+                                    </Calculation>
+                                </Calculation>
+                            </Parameter>
+                            <Include All=\"True\"></Include>
+                        </Pages>
+                    </Options>
+                </Parameter>
+                <Parameter type=\"Boolean\">
+                    <Boolean type=\"Ordner erstellen\" id=\"512\" value=\"False\"></Boolean>
+                </Parameter>
+            </ParameterValues>
+        </Step>
+        ";
+
+        let expected_output = Some("Datensätze als PDF speichern [ Restore ; An vorhandene PDF anhängen: ON ; Mit Dialog: OFF ; Ordner erstellen: OFF ; Records: Alle aufgerufenen Datensätze ; Path: \"$Path\" ; Automatically open ]".to_string());
+        assert_eq!(sanitize(xml_input.trim()), expected_output);
+    }
+
+    #[test]
+    fn test_sanitize_excel() {
+        let xml_input = "
+        <Step id=\"143\" name=\"Datensätze als Excel speichern\" enable=\"True\">
+            <Options>1644183712</Options>
+            <ParameterValues membercount=\"5\">
+                <Parameter type=\"Restore\">
+                    <Restore value=\"True\"></Restore>
+                </Parameter>
+                <Parameter type=\"Boolean\">
+                    <Boolean type=\"Mit Dialog\" id=\"128\" value=\"False\"></Boolean>
+                </Parameter>
+                <Parameter type=\"UniversalPathList\">
+                    <UniversalPathList fileType=\"XLXE\" AutoOpen=\"False\" CreateMail=\"True\" membercount=\"1\">
+                        <Excel name=\"Alle aufgerufenen Datensätze\" value=\"1\">
+                            <Boolean type=\"Feldnamen als Spaltennamen verwenden\" value=\"True\"></Boolean>
+                        </Excel>
+                        <ObjectList>
+                            <Location><![CDATA[$Path]]></Location>
+                        </ObjectList>
+                    </UniversalPathList>
+                </Parameter>
+                <Parameter type=\"Options\">
+                    <Options>
+                        <Save type=\"Alle aufgerufenen Datensätze\" value=\"1\" useFieldNames=\"True\"></Save>
+                        <Parameter type=\"Worksheet\">
+                            <Calculation datatype=\"1\" position=\"0\">
+                                <Calculation>
+                                    <Text><![CDATA[$WKS1]]></Text>
+                                    <ChunkList hash=\"A51D8078F280FBA247563236BF8389E6\">
+                                        <Chunk type=\"VariableReference\">$WKS1</Chunk>
+                                    </ChunkList>
+                                </Calculation>
+                            </Calculation>
+                        </Parameter>
+                        <Parameter type=\"Title\">
+                            <Calculation datatype=\"1\" position=\"1\">
+                                <Calculation>
+                                    <Text><![CDATA[$Title]]></Text>
+                                    <ChunkList hash=\"04FF8665C2DD0C13AFEDB6FC40B80FDA\">
+                                        <Chunk type=\"VariableReference\">$Title</Chunk>
+                                    </ChunkList>
+                                </Calculation>
+                            </Calculation>
+                        </Parameter>
+                    </Options>
+                </Parameter>
+                <Parameter type=\"Boolean\">
+                    <Boolean type=\"Ordner erstellen\" id=\"512\" value=\"False\"></Boolean>
+                </Parameter>
+            </ParameterValues>
+        </Step>
+        ";
+
+        let expected_output = Some("Datensätze als Excel speichern [ Restore ; Mit Dialog: OFF ; Ordner erstellen: OFF ; Records: Alle aufgerufenen Datensätze ; Use field names as column names ; Worksheet: $WKS1 ; Title: $Title ; Path: \"$Path\" ; Create email ]".to_string());
+        assert_eq!(sanitize(xml_input.trim()), expected_output);
+    }
+}
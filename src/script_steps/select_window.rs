@@ -0,0 +1,91 @@
+use crate::calculations::window_reference::WindowReference;
+use crate::utils::attributes::get_attribute;
+use quick_xml::events::Event;
+use quick_xml::Reader;
+
+pub fn sanitize(step: &str) -> Option<String> {
+    let mut name = String::new();
+    let mut window_reference = String::new();
+
+    let mut reader = Reader::from_str(step);
+    reader.trim_text(true);
+    let mut buf: Vec<u8> = Vec::new();
+    loop {
+        match reader.read_event_into(&mut buf) {
+            Err(_) => continue,
+            Ok(Event::Eof) => break,
+            Ok(Event::Start(e)) => match e.name().as_ref() {
+                b"Step" => name = get_attribute(&e, "name").unwrap().to_string(),
+                b"WindowReference" => {
+                    window_reference = WindowReference::from_xml(&mut reader, &e)
+                        .unwrap()
+                        .display()
+                        .unwrap()
+                }
+                _ => {}
+            },
+            _ => {}
+        }
+        buf.clear()
+    }
+
+    if name.is_empty() {
+        None
+    } else {
+        Some(format!("{} [ {} ]", name, window_reference))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sanitize_current_window() {
+        let xml_input = "
+        <Step id=\"123\" name=\"Fenster aktivieren\" enable=\"True\">
+            <Options>-2147483646</Options>
+            <ParameterValues membercount=\"1\">
+                <Parameter type=\"WindowReference\">
+                    <WindowReference>
+                        <Select kind=\"0\" type=\"current\"></Select>
+                    </WindowReference>
+                </Parameter>
+            </ParameterValues>
+        </Step>
+        ";
+
+        let expected_output = Some("Fenster aktivieren [ Current window ]".to_string());
+        assert_eq!(sanitize(xml_input.trim()), expected_output);
+    }
+
+    #[test]
+    fn test_sanitize_by_name() {
+        let xml_input = "
+        <Step id=\"123\" name=\"Fenster aktivieren\" enable=\"True\">
+            <Options>-2147467262</Options>
+            <ParameterValues membercount=\"1\">
+                <Parameter type=\"WindowReference\">
+                    <WindowReference>
+                        <Select kind=\"1\" type=\"Calculated\">
+                            <Name current=\"False\">
+                                <Calculation datatype=\"1\" position=\"0\">
+                                    <Calculation>
+                                        <Text><![CDATA[\"Foo Bar\"]]></Text>
+                                        <ChunkList hash=\"525D18B1E8FB2D7DFFF28F99FBDA6054\">
+                                            <Chunk type=\"NoRef\">&quot;Foo Bar&quot;</Chunk>
+                                        </ChunkList>
+                                    </Calculation>
+                                </Calculation>
+                            </Name>
+                        </Select>
+                    </WindowReference>
+                </Parameter>
+            </ParameterValues>
+        </Step>
+        ";
+
+        let expected_output = Some("Fenster aktivieren [ Name: \"Foo Bar\" ]".to_string());
+        assert_eq!(sanitize(xml_input.trim()), expected_output);
+    }
+}
@@ -0,0 +1,143 @@
+use crate::calculations::calculation::Calculation;
+use crate::calculations::path_list::UniversalPathList;
+use crate::utils::attributes::get_attribute;
+use crate::utils::xml_utils::text_to_string;
+use quick_xml::events::Event;
+use quick_xml::Reader;
+
+const EVENT_OPTIONS: [(&str, &str); 3] = [
+    ("Foreground", "Bring target application to foreground"),
+    ("Wait", "Wait for event completion"),
+    ("Copy", "Copy event result"),
+];
+
+pub fn sanitize(step: &str) -> Option<String> {
+    let mut name = String::new();
+    let mut params: Vec<String> = Vec::new();
+    let mut source_type = String::new();
+    let mut in_source_text = false;
+
+    let mut reader = Reader::from_str(step);
+    reader.trim_text(true);
+    let mut buf: Vec<u8> = Vec::new();
+    loop {
+        match reader.read_event_into(&mut buf) {
+            Err(_) => continue,
+            Ok(Event::Eof) => break,
+            Ok(Event::Start(e)) => match e.name().as_ref() {
+                b"Step" => name = get_attribute(&e, "name").unwrap().to_string(),
+                b"Target" => {
+                    let target = get_attribute(&e, "value").unwrap_or_default();
+                    if !target.is_empty() {
+                        params.push(format!("Target: {}", target));
+                    }
+                }
+                b"Send" => {
+                    let class = get_attribute(&e, "classString").unwrap_or_default();
+                    let id = get_attribute(&e, "idString").unwrap_or_default();
+                    // empty events and Windows events have no Apple event class
+                    let is_empty = ["class", "id"]
+                        .iter()
+                        .any(|attribute| get_attribute(&e, attribute).as_deref() == Some("0"))
+                        || class == "????"
+                        || id == "????";
+                    if !is_empty {
+                        params.push(format!("Event: {} {}", class, id));
+                    }
+                }
+                b"Source" => source_type = get_attribute(&e, "type").unwrap_or_default(),
+                b"UniversalPathList" => params.push(format!(
+                    "Document: {}",
+                    UniversalPathList::from_xml(&mut reader, &e)
+                        .unwrap()
+                        .display()
+                        .unwrap()
+                )),
+                b"Calculation" if source_type == "Calculation" => params.push(format!(
+                    "Calculation: {}",
+                    Calculation::from_xml(&mut reader, &e).unwrap()
+                )),
+                b"Text" if source_type == "Text" => in_source_text = true,
+                b"Options" => {
+                    for (attribute, label) in EVENT_OPTIONS {
+                        if get_attribute(&e, attribute).unwrap_or_default() == "True" {
+                            params.push(label.to_string());
+                        }
+                    }
+                }
+                _ => {}
+            },
+            Ok(Event::Text(e)) if in_source_text => {
+                params.push(format!("Text: {}", text_to_string(&e)));
+            }
+            Ok(Event::End(e)) if e.name().as_ref() == b"Text" => in_source_text = false,
+            _ => {}
+        }
+        buf.clear()
+    }
+
+    if name.is_empty() {
+        None
+    } else {
+        Some(format!("{} [ {} ]", name, params.join(" ; ")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sanitize_document() {
+        let xml_input = "
+        <Step id=\"57\" name=\"Event senden\" enable=\"True\">
+            <ParameterValues membercount=\"1\">
+                <Parameter>
+                    <Target value=\"TextMate.app\"></Target>
+                    <Send name=\"Dokument öffnen\" classString=\"aevt\" idString=\"odoc\" class=\"1634039412\" id=\"1868853091\"></Send>
+                    <Source type=\"Document\">
+                        <Document>
+                            <UniversalPathList membercount=\"1\">
+                                <ObjectList>
+                                    <Location><![CDATA[file:fmxmlsnippet.xml]]></Location>
+                                </ObjectList>
+                            </UniversalPathList>
+                        </Document>
+                    </Source>
+                    <Options Foreground=\"True\" Wait=\"True\" Copy=\"False\"></Options>
+                </Parameter>
+            </ParameterValues>
+        </Step>
+        ";
+
+        let expected_output = Some("Event senden [ Target: TextMate.app ; Event: aevt odoc ; Document: \"file:fmxmlsnippet.xml\" ; Bring target application to foreground ; Wait for event completion ]".to_string());
+        assert_eq!(sanitize(xml_input.trim()), expected_output);
+    }
+
+    #[test]
+    fn test_sanitize_calculation() {
+        let xml_input = "
+        <Step id=\"57\" name=\"Event senden\" enable=\"True\">
+            <ParameterValues membercount=\"1\">
+                <Parameter>
+                    <Target value=\"\"></Target>
+                    <Send classString=\"????\" idString=\"????\" class=\"0\" id=\"0\"></Send>
+                    <Source type=\"Calculation\">
+                        <Calculation>
+                            <Calculation datatype=\"1\" position=\"0\">
+                                <Calculation>
+                                    <Text><![CDATA[$my.powershell]]></Text>
+                                </Calculation>
+                            </Calculation>
+                        </Calculation>
+                    </Source>
+                    <Options Foreground=\"False\" Wait=\"False\" Copy=\"False\"></Options>
+                </Parameter>
+            </ParameterValues>
+        </Step>
+        ";
+
+        let expected_output = Some("Event senden [ Calculation: $my.powershell ]".to_string());
+        assert_eq!(sanitize(xml_input.trim()), expected_output);
+    }
+}
@@ -0,0 +1,252 @@
+use crate::calculations::calculation::Calculation;
+use crate::calculations::field_reference::FieldReference;
+use crate::calculations::path_list::UniversalPathList;
+use crate::utils::attributes::get_attribute;
+use quick_xml::events::Event;
+use quick_xml::Reader;
+
+const MAIL_FIELDS: [(&str, &str); 11] = [
+    ("Name", "From name"),
+    ("Email", "From email"),
+    ("ReplyTo", "Reply to"),
+    ("Server", "SMTP server"),
+    ("Port", "Port"),
+    ("UserName", "User name"),
+    ("To", "To"),
+    ("CC", "CC"),
+    ("BCC", "BCC"),
+    ("Subject", "Subject"),
+    ("Message", "Message"),
+];
+
+#[derive(Debug, Default)]
+struct XmlTrackingState {
+    in_smtp: bool,
+    field: Option<String>,
+    collect_addresses: bool,
+}
+
+pub fn sanitize(step: &str) -> Option<String> {
+    let mut name = String::new();
+    let mut params: Vec<String> = Vec::new();
+    let mut smtp: Vec<String> = Vec::new();
+    let mut send: Vec<String> = Vec::new();
+    let mut via = String::new();
+    let mut attachments = UniversalPathList::default();
+    let mut xml_pos = XmlTrackingState::default();
+
+    let mut reader = Reader::from_str(step);
+    reader.trim_text(true);
+    let mut buf: Vec<u8> = Vec::new();
+    loop {
+        match reader.read_event_into(&mut buf) {
+            Err(_) => continue,
+            Ok(Event::Eof) => break,
+            Ok(Event::Start(e)) => match e.name().as_ref() {
+                b"Step" => name = get_attribute(&e, "name").unwrap().to_string(),
+                b"Boolean" => {
+                    params.push(format!(
+                        "{}: {}",
+                        get_attribute(&e, "type").unwrap_or_default(),
+                        match get_attribute(&e, "value").unwrap_or_default().as_str() {
+                            "True" => "ON",
+                            _ => "OFF",
+                        }
+                    ));
+                }
+                b"SMTP" => xml_pos.in_smtp = true,
+                b"Send" => {
+                    via = match get_attribute(&e, "SMTP").unwrap_or_default().as_str() {
+                        "True" => "Via: SMTP server".to_string(),
+                        _ => "Via: E-mail client".to_string(),
+                    };
+                    if get_attribute(&e, "OAuthAuthentication").unwrap_or_default() == "True" {
+                        via.push_str(" (OAuth)");
+                    }
+                }
+                b"Multiple" => {
+                    if get_attribute(&e, "value").unwrap_or_default() == "True" {
+                        send.push("Multiple emails".to_string());
+                    }
+                }
+                b"Encryption" => smtp.push(format!(
+                    "Encryption: {}",
+                    get_attribute(&e, "name").unwrap_or_default()
+                )),
+                b"Authentication" => smtp.push(format!(
+                    "Authentication: {}",
+                    get_attribute(&e, "name").unwrap_or_default()
+                )),
+                b"CollectAddresses" => {
+                    xml_pos.collect_addresses =
+                        get_attribute(&e, "value").unwrap_or_default() == "True";
+                }
+                b"Password" => xml_pos.field = None,
+                b"Calculation" | b"FieldReference" if xml_pos.field.is_some() => {
+                    let value = match e.name().as_ref() {
+                        b"FieldReference" => FieldReference::from_xml(&mut reader, &e)
+                            .unwrap()
+                            .display()
+                            .unwrap(),
+                        _ => Calculation::from_xml(&mut reader, &e).unwrap(),
+                    };
+                    let label = xml_pos.field.take().unwrap();
+                    let value = match xml_pos.collect_addresses {
+                        true => format!("{}: {} (collect addresses)", label, value),
+                        false => format!("{}: {}", label, value),
+                    };
+                    match xml_pos.in_smtp {
+                        true => smtp.push(value),
+                        false => send.push(value),
+                    }
+                }
+                b"UniversalPathList" => {
+                    attachments = UniversalPathList::from_xml(&mut reader, &e).unwrap();
+                }
+                element => {
+                    xml_pos.field = MAIL_FIELDS
+                        .iter()
+                        .find(|(tag, _)| tag.as_bytes() == element)
+                        .map(|(_, label)| label.to_string())
+                }
+            },
+            Ok(Event::End(e)) => match e.name().as_ref() {
+                b"SMTP" => xml_pos.in_smtp = false,
+                b"To" | b"CC" | b"BCC" => xml_pos.collect_addresses = false,
+                _ => {}
+            },
+            _ => {}
+        }
+        buf.clear()
+    }
+
+    if !via.is_empty() {
+        params.push(via);
+    }
+    params.extend(smtp);
+    params.extend(send);
+    if !attachments.paths.is_empty() {
+        params.push(format!("Attachment: {}", attachments.display().unwrap()));
+    }
+
+    if name.is_empty() {
+        None
+    } else {
+        Some(format!("{} [ {} ]", name, params.join(" ; ")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sanitize_via_smtp() {
+        let xml_input = "
+        <Step id=\"63\" name=\"E-Mail senden\" enable=\"True\">
+            <Options>16416</Options>
+            <ParameterValues membercount=\"1\">
+                <Parameter type=\"Email\">
+                    <Boolean type=\"Ohne Dialogfeld\" value=\"True\"></Boolean>
+                    <SMTP>
+                        <Name>
+                            <Calculation datatype=\"1\" position=\"5\">
+                                <Calculation>
+                                    <Text><![CDATA[$User]]></Text>
+                                </Calculation>
+                            </Calculation>
+                        </Name>
+                        <ReplyTo></ReplyTo>
+                        <Server>
+                            <Calculation datatype=\"1\" position=\"8\">
+                                <Calculation>
+                                    <Text><![CDATA[$SMTPServer]]></Text>
+                                </Calculation>
+                            </Calculation>
+                        </Server>
+                        <Encryption name=\"SSL\" value=\"1\"></Encryption>
+                        <Authentication name=\"Passwort (Klartext)\" value=\"2\"></Authentication>
+                        <Password>
+                            <Calculation datatype=\"1\" position=\"11\">
+                                <Calculation>
+                                    <Text><![CDATA[$Password]]></Text>
+                                </Calculation>
+                            </Calculation>
+                        </Password>
+                    </SMTP>
+                    <Send OAuthAuthentication=\"False\" SMTP=\"True\">
+                        <Multiple value=\"False\"></Multiple>
+                        <To>
+                            <CollectAddresses value=\"False\"></CollectAddresses>
+                            <Calculation datatype=\"1\" position=\"0\">
+                                <Calculation>
+                                    <Text><![CDATA[$To]]></Text>
+                                </Calculation>
+                            </Calculation>
+                        </To>
+                        <CC>
+                            <CollectAddresses value=\"False\"></CollectAddresses>
+                        </CC>
+                        <BCC>
+                            <CollectAddresses value=\"False\"></CollectAddresses>
+                        </BCC>
+                        <Subject>
+                            <Calculation datatype=\"1\" position=\"3\">
+                                <Calculation>
+                                    <Text><![CDATA[$Subject]]></Text>
+                                </Calculation>
+                            </Calculation>
+                        </Subject>
+                        <Message>
+                            <Calculation datatype=\"1\" position=\"4\">
+                                <Calculation>
+                                    <Text><![CDATA[$Message]]></Text>
+                                </Calculation>
+                            </Calculation>
+                        </Message>
+                    </Send>
+                    <UniversalPathList membercount=\"1\">
+                        <ObjectList>
+                            <Location><![CDATA[$FilePath]]></Location>
+                        </ObjectList>
+                    </UniversalPathList>
+                </Parameter>
+            </ParameterValues>
+        </Step>
+        ";
+
+        let expected_output = Some("E-Mail senden [ Ohne Dialogfeld: ON ; Via: SMTP server ; From name: $User ; SMTP server: $SMTPServer ; Encryption: SSL ; Authentication: Passwort (Klartext) ; To: $To ; Subject: $Subject ; Message: $Message ; Attachment: \"$FilePath\" ]".to_string());
+        assert_eq!(sanitize(xml_input.trim()), expected_output);
+    }
+
+    #[test]
+    fn test_sanitize_via_client() {
+        let xml_input = "
+        <Step id=\"63\" name=\"E-Mail senden\" enable=\"True\">
+            <ParameterValues membercount=\"1\">
+                <Parameter type=\"Email\">
+                    <Boolean type=\"Mit Dialog\" position=\"156\" value=\"True\"></Boolean>
+                    <Send OAuthAuthentication=\"False\" SMTP=\"False\">
+                        <Multiple value=\"False\"></Multiple>
+                        <To>
+                            <CollectAddresses value=\"False\"></CollectAddresses>
+                        </To>
+                        <CC>
+                            <CollectAddresses value=\"False\"></CollectAddresses>
+                        </CC>
+                        <BCC>
+                            <CollectAddresses value=\"False\"></CollectAddresses>
+                        </BCC>
+                        <Subject></Subject>
+                        <Message></Message>
+                    </Send>
+                </Parameter>
+            </ParameterValues>
+        </Step>
+        ";
+
+        let expected_output =
+            Some("E-Mail senden [ Mit Dialog: ON ; Via: E-mail client ]".to_string());
+        assert_eq!(sanitize(xml_input.trim()), expected_output);
+    }
+}
@@ -0,0 +1,98 @@
+use crate::calculations::calculation::Calculation;
+use crate::utils::attributes::get_attribute;
+use quick_xml::events::Event;
+use quick_xml::Reader;
+
+pub fn sanitize(step: &str) -> Option<String> {
+    let mut name = String::new();
+    let mut calculations: Vec<String> = Vec::new();
+
+    let mut reader = Reader::from_str(step);
+    reader.trim_text(true);
+    let mut buf: Vec<u8> = Vec::new();
+    loop {
+        match reader.read_event_into(&mut buf) {
+            Err(_) => continue,
+            Ok(Event::Eof) => break,
+            Ok(Event::Start(e)) => match e.name().as_ref() {
+                b"Step" => name = get_attribute(&e, "name").unwrap().to_string(),
+                b"Parameter" if get_attribute(&e, "type").unwrap_or_default() == "Calculation" => {
+                    let calculation = Calculation::from_xml(&mut reader, &e).unwrap();
+                    if !calculation.is_empty() {
+                        calculations.push(calculation);
+                    }
+                }
+                _ => {}
+            },
+            _ => {}
+        }
+        buf.clear()
+    }
+
+    if name.is_empty() {
+        None
+    } else if calculations.is_empty() {
+        Some(format!("{} []", name))
+    } else {
+        Some(format!("{} [ {} ]", name, calculations.join(" ; ")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sanitize() {
+        let xml_input = "
+        <Step id=\"147\" name=\"Feld nach Namen einstellen\" enable=\"True\">
+            <Options>134234112</Options>
+            <ParameterValues membercount=\"3\">
+                <Parameter type=\"Boolean\">
+                    <Boolean type=\"Zielfeld angeben\" id=\"134217728\" value=\"True\"></Boolean>
+                </Parameter>
+                <Parameter type=\"Calculation\">
+                    <Calculation datatype=\"1\" position=\"1\">
+                        <Calculation>
+                            <Text><![CDATA[$FieldName]]></Text>
+                            <ChunkList hash=\"974F7CA0772E783561468BAC4492D216\">
+                                <Chunk type=\"VariableReference\">$FieldName</Chunk>
+                            </ChunkList>
+                        </Calculation>
+                    </Calculation>
+                </Parameter>
+                <Parameter type=\"Calculation\">
+                    <Calculation datatype=\"1\" position=\"0\">
+                        <Calculation>
+                            <Text><![CDATA[$Value]]></Text>
+                            <ChunkList hash=\"1277603E982B7574A4A72E68E88EE093\">
+                                <Chunk type=\"VariableReference\">$Value</Chunk>
+                            </ChunkList>
+                        </Calculation>
+                    </Calculation>
+                </Parameter>
+            </ParameterValues>
+        </Step>
+        ";
+
+        let expected_output =
+            Some("Feld nach Namen einstellen [ $FieldName ; $Value ]".to_string());
+        assert_eq!(sanitize(xml_input.trim()), expected_output);
+    }
+
+    #[test]
+    fn test_sanitize_without_calculations() {
+        let xml_input = "
+        <Step id=\"147\" name=\"Feld nach Namen einstellen\" enable=\"True\">
+            <ParameterValues membercount=\"1\">
+                <Parameter type=\"Boolean\">
+                    <Boolean type=\"Zielfeld angeben\" id=\"134217728\" value=\"False\"></Boolean>
+                </Parameter>
+            </ParameterValues>
+        </Step>
+        ";
+
+        let expected_output = Some("Feld nach Namen einstellen []".to_string());
+        assert_eq!(sanitize(xml_input.trim()), expected_output);
+    }
+}
@@ -0,0 +1,90 @@
+use crate::calculations::window_reference::WindowReference;
+use crate::utils::attributes::get_attribute;
+use quick_xml::events::Event;
+use quick_xml::Reader;
+
+pub fn sanitize(step: &str) -> Option<String> {
+    let mut name = String::new();
+    let mut window_reference = WindowReference::default();
+
+    let mut reader = Reader::from_str(step);
+    reader.trim_text(true);
+    let mut buf: Vec<u8> = Vec::new();
+    loop {
+        match reader.read_event_into(&mut buf) {
+            Err(_) => continue,
+            Ok(Event::Eof) => break,
+            Ok(Event::Start(e)) => match e.name().as_ref() {
+                b"Step" => name = get_attribute(&e, "name").unwrap().to_string(),
+                b"WindowReference" => {
+                    window_reference = WindowReference::from_xml(&mut reader, &e).unwrap()
+                }
+                _ => {}
+            },
+            _ => {}
+        }
+        buf.clear()
+    }
+
+    if name.is_empty() {
+        None
+    } else if window_reference.rename.is_empty() {
+        Some(format!(
+            "{} [ {} ]",
+            name,
+            window_reference.display().unwrap()
+        ))
+    } else {
+        Some(format!(
+            "{} [ {} ; New title: {} ]",
+            name,
+            window_reference.display().unwrap(),
+            window_reference.rename
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sanitize() {
+        let xml_input = "
+        <Step id=\"124\" name=\"Fenstertitel setzen\" enable=\"True\">
+            <Options>-2147467262</Options>
+            <ParameterValues membercount=\"1\">
+                <Parameter type=\"WindowReference\">
+                    <WindowReference>
+                        <Select kind=\"1\" type=\"Calculated\">
+                            <Name current=\"True\">
+                                <Calculation datatype=\"1\" position=\"0\">
+                                    <Calculation>
+                                        <Text><![CDATA[$FensterNameAlt]]></Text>
+                                        <ChunkList hash=\"0D0E1C4F6BC1F8C3B7B5E0C2C2B7A0E1\">
+                                            <Chunk type=\"VariableReference\">$FensterNameAlt</Chunk>
+                                        </ChunkList>
+                                    </Calculation>
+                                </Calculation>
+                            </Name>
+                        </Select>
+                        <Rename>
+                            <Calculation datatype=\"1\" position=\"1\">
+                                <Calculation>
+                                    <Text><![CDATA[$FensterNameNeu]]></Text>
+                                    <ChunkList hash=\"A1C6C0A4B2E2D5B8B0B9C0E7D4F8A6B1\">
+                                        <Chunk type=\"VariableReference\">$FensterNameNeu</Chunk>
+                                    </ChunkList>
+                                </Calculation>
+                            </Calculation>
+                        </Rename>
+                    </WindowReference>
+                </Parameter>
+            </ParameterValues>
+        </Step>
+        ";
+
+        let expected_output = Some("Fenstertitel setzen [ Name: $FensterNameAlt ; Current file ; New title: $FensterNameNeu ]".to_string());
+        assert_eq!(sanitize(xml_input.trim()), expected_output);
+    }
+}
@@ -0,0 +1,274 @@
+use crate::calculations::calculation::Calculation;
+use crate::calculations::field_reference::FieldReference;
+use crate::utils::attributes::get_attribute;
+use quick_xml::events::Event;
+use quick_xml::Reader;
+
+#[derive(Debug, Default)]
+struct DialogButton {
+    name: String,
+    label: String,
+    commit_label: String,
+    commit: bool,
+}
+
+#[derive(Debug, Default)]
+struct InputField {
+    name: String,
+    target: String,
+    label: String,
+    password_label: String,
+    password: bool,
+}
+
+#[derive(Debug, Default, PartialEq)]
+enum ParameterScope {
+    #[default]
+    None,
+    Button,
+    Field,
+}
+
+pub fn sanitize(step: &str) -> Option<String> {
+    let mut name = String::new();
+    let mut title = String::new();
+    let mut message = String::new();
+    let mut buttons: Vec<DialogButton> = Vec::new();
+    let mut fields: Vec<InputField> = Vec::new();
+    let mut scope = ParameterScope::None;
+
+    let mut reader = Reader::from_str(step);
+    reader.trim_text(true);
+    let mut buf: Vec<u8> = Vec::new();
+    loop {
+        match reader.read_event_into(&mut buf) {
+            Err(_) => continue,
+            Ok(Event::Eof) => break,
+            Ok(Event::Start(e)) => match e.name().as_ref() {
+                b"Step" => name = get_attribute(&e, "name").unwrap().to_string(),
+                b"Parameter" => {
+                    let parameter_type = get_attribute(&e, "type").unwrap_or_default();
+                    match parameter_type.as_str() {
+                        "Title" => title = Calculation::from_xml(&mut reader, &e).unwrap(),
+                        "Message" => message = Calculation::from_xml(&mut reader, &e).unwrap(),
+                        "Button1" | "Button2" | "Button3" => {
+                            scope = ParameterScope::Button;
+                            buttons.push(DialogButton {
+                                name: parameter_type,
+                                label: get_attribute(&e, "value").unwrap_or_default(),
+                                ..Default::default()
+                            });
+                        }
+                        "Field1" | "Field2" | "Field3" => {
+                            scope = ParameterScope::Field;
+                            fields.push(InputField {
+                                name: parameter_type,
+                                ..Default::default()
+                            });
+                        }
+                        "Label" => {
+                            let label = Calculation::from_xml(&mut reader, &e).unwrap();
+                            if let Some(field) = fields.last_mut() {
+                                field.label = label;
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+                b"FieldReference" => {
+                    let target = FieldReference::from_xml(&mut reader, &e)
+                        .unwrap()
+                        .display()
+                        .unwrap();
+                    if let Some(field) = fields.last_mut() {
+                        field.target = target;
+                    }
+                }
+                b"Variable" => {
+                    if let Some(field) = fields.last_mut() {
+                        field.target = get_attribute(&e, "value").unwrap_or_default();
+                    }
+                }
+                b"Boolean" => {
+                    let label = get_attribute(&e, "type").unwrap_or_default();
+                    let value = get_attribute(&e, "value").unwrap_or_default() == "True";
+                    match scope {
+                        ParameterScope::Button => {
+                            if let Some(button) = buttons.last_mut() {
+                                button.commit_label = label;
+                                button.commit = value;
+                            }
+                        }
+                        ParameterScope::Field => {
+                            if let Some(field) = fields.last_mut() {
+                                field.password_label = label;
+                                field.password = value;
+                            }
+                        }
+                        ParameterScope::None => {}
+                    }
+                }
+                _ => {}
+            },
+            _ => {}
+        }
+        buf.clear()
+    }
+
+    let mut params: Vec<String> = Vec::new();
+    if !title.is_empty() {
+        params.push(format!("Title: {}", title));
+    }
+    if !message.is_empty() {
+        params.push(format!("Message: {}", message));
+    }
+    for button in buttons.iter().filter(|b| !b.label.is_empty()) {
+        params.push(format!(
+            "{}: \"{}\" ({}: {})",
+            button.name,
+            button.label,
+            button.commit_label,
+            match button.commit {
+                true => "ON",
+                false => "OFF",
+            }
+        ));
+    }
+    for field in fields.iter().filter(|f| !f.target.is_empty()) {
+        let mut options: Vec<String> = Vec::new();
+        if !field.label.is_empty() {
+            options.push(format!("Label: {}", field.label));
+        }
+        options.push(format!(
+            "{}: {}",
+            field.password_label,
+            match field.password {
+                true => "ON",
+                false => "OFF",
+            }
+        ));
+        params.push(format!(
+            "{}: {} ({})",
+            field.name,
+            field.target,
+            options.join(", ")
+        ));
+    }
+
+    if name.is_empty() {
+        None
+    } else if params.is_empty() {
+        Some(format!("{} []", name))
+    } else {
+        Some(format!("{} [ {} ]", name, params.join(" ; ")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sanitize() {
+        let xml_input = "
+        <Step id=\"87\" name=\"Eigenes Dialogfeld anzeigen\" enable=\"True\">
+            <Options>16388</Options>
+            <ParameterValues membercount=\"5\">
+                <Parameter type=\"Title\">
+                    <Calculation datatype=\"1\" position=\"0\">
+                        <Calculation>
+                            <Text><![CDATA[\"Error\"]]></Text>
+                            <ChunkList hash=\"F44200DB64CC5E44F7B9D3970A81E5A0\">
+                                <Chunk type=\"NoRef\">&quot;Error&quot;</Chunk>
+                            </ChunkList>
+                        </Calculation>
+                    </Calculation>
+                </Parameter>
+                <Parameter type=\"Message\">
+                    <Calculation datatype=\"1\" position=\"1\">
+                        <Calculation>
+                            <Text><![CDATA[\"Server Replied: \" & $my.response]]></Text>
+                            <ChunkList hash=\"F0A63435B76CAC19C503757CB3A8451D\">
+                                <Chunk type=\"NoRef\">&quot;Server Replied: &quot; &amp; </Chunk>
+                                <Chunk type=\"VariableReference\">$my.response</Chunk>
+                            </ChunkList>
+                        </Calculation>
+                    </Calculation>
+                </Parameter>
+                <Parameter type=\"Button1\" value=\"OK\">
+                    <Boolean type=\"Commit\" value=\"True\"></Boolean>
+                </Parameter>
+                <Parameter type=\"Button2\">
+                    <Boolean type=\"Commit\" value=\"False\"></Boolean>
+                </Parameter>
+                <Parameter type=\"Button3\">
+                    <Boolean type=\"Commit\" value=\"False\"></Boolean>
+                </Parameter>
+            </ParameterValues>
+        </Step>
+        ";
+
+        let expected_output = Some("Eigenes Dialogfeld anzeigen [ Title: \"Error\" ; Message: \"Server Replied: \" & $my.response ; Button1: \"OK\" (Commit: ON) ]".to_string());
+        assert_eq!(sanitize(xml_input.trim()), expected_output);
+    }
+
+    #[test]
+    fn test_sanitize_with_input_fields() {
+        let xml_input = "
+        <Step id=\"87\" name=\"Eigenes Dialogfeld anzeigen\" enable=\"True\">
+            <Options>16389</Options>
+            <ParameterValues membercount=\"7\">
+                <Parameter type=\"Message\">
+                    <Calculation datatype=\"1\" position=\"1\">
+                        <Calculation>
+                            <Text><![CDATA[$Message]]></Text>
+                            <ChunkList hash=\"2C009C346321EF6F03259BFD277B7929\">
+                                <Chunk type=\"VariableReference\">$Message</Chunk>
+                            </ChunkList>
+                        </Calculation>
+                    </Calculation>
+                </Parameter>
+                <Parameter type=\"Button1\" value=\"OK\">
+                    <Boolean type=\"Commit\" value=\"True\"></Boolean>
+                </Parameter>
+                <Parameter type=\"Button2\" value=\"Cancel\">
+                    <Boolean type=\"Commit\" value=\"False\"></Boolean>
+                </Parameter>
+                <Parameter type=\"Button3\">
+                    <Boolean type=\"Commit\" value=\"False\"></Boolean>
+                </Parameter>
+                <Parameter type=\"Field1\">
+                    <Parameter type=\"Target\">
+                        <FieldReference id=\"6\" name=\"_gInputText1\">
+                            <repetition value=\"1\"></repetition>
+                            <TableOccurrenceReference id=\"1065123\" name=\"_Home\"></TableOccurrenceReference>
+                        </FieldReference>
+                    </Parameter>
+                    <Boolean type=\"Passwort\" value=\"True\"></Boolean>
+                    <Parameter type=\"Label\">
+                        <Calculation datatype=\"1\" position=\"2\">
+                            <Calculation>
+                                <Text><![CDATA[\"Old Password\"]]></Text>
+                                <ChunkList hash=\"A8E6E6A3FCD4592C4E85DE9A552F1080\">
+                                    <Chunk type=\"NoRef\">&quot;Old Password&quot;</Chunk>
+                                </ChunkList>
+                            </Calculation>
+                        </Calculation>
+                    </Parameter>
+                </Parameter>
+                <Parameter type=\"Field2\">
+                    <Parameter type=\"Target\">
+                        <Variable value=\"$Input2\">
+                            <repetition value=\"1\"></repetition>
+                        </Variable>
+                    </Parameter>
+                    <Boolean type=\"Passwort\" value=\"False\"></Boolean>
+                </Parameter>
+            </ParameterValues>
+        </Step>
+        ";
+
+        let expected_output = Some("Eigenes Dialogfeld anzeigen [ Message: $Message ; Button1: \"OK\" (Commit: ON) ; Button2: \"Cancel\" (Commit: OFF) ; Field1: _Home::_gInputText1 (Label: \"Old Password\", Passwort: ON) ; Field2: $Input2 (Passwort: OFF) ]".to_string());
+        assert_eq!(sanitize(xml_input.trim()), expected_output);
+    }
+}
@@ -0,0 +1,203 @@
+use crate::calculations::field_reference::FieldReference;
+use crate::utils::attributes::get_attribute;
+use quick_xml::events::Event;
+use quick_xml::Reader;
+
+#[derive(Debug, Default)]
+struct SortField {
+    field_reference: String,
+    order: String,
+    value_list: String,
+    summary_field: String,
+    language: String,
+}
+
+#[derive(Debug, Default)]
+struct XmlTrackingState {
+    in_primary_field: bool,
+    in_summary_field: bool,
+}
+
+pub fn sanitize(step: &str) -> Option<String> {
+    let mut name = String::new();
+    let mut params: Vec<String> = Vec::new();
+    let mut restore = false;
+    let mut keep_sorted = false;
+    let mut sort_fields: Vec<SortField> = Vec::new();
+    let mut xml_pos = XmlTrackingState::default();
+
+    let mut reader = Reader::from_str(step);
+    reader.trim_text(true);
+    let mut buf: Vec<u8> = Vec::new();
+    loop {
+        match reader.read_event_into(&mut buf) {
+            Err(_) => continue,
+            Ok(Event::Eof) => break,
+            Ok(Event::Start(e)) => match e.name().as_ref() {
+                b"Step" => name = get_attribute(&e, "name").unwrap().to_string(),
+                b"Boolean" => {
+                    params.push(format!(
+                        "{}: {}",
+                        get_attribute(&e, "type").unwrap_or_default(),
+                        match get_attribute(&e, "value").unwrap_or_default().as_str() {
+                            "True" => "ON",
+                            _ => "OFF",
+                        }
+                    ));
+                }
+                b"Restore" => restore = get_attribute(&e, "value").unwrap_or_default() == "True",
+                b"SortSpecification" => {
+                    keep_sorted = get_attribute(&e, "maintain").unwrap_or_default() == "True"
+                }
+                b"Sort" => sort_fields.push(SortField {
+                    order: get_attribute(&e, "type").unwrap_or_default(),
+                    ..Default::default()
+                }),
+                b"PrimaryField" => xml_pos.in_primary_field = true,
+                b"SummaryField" => xml_pos.in_summary_field = true,
+                b"FieldReference" => {
+                    let field_reference = FieldReference::from_xml(&mut reader, &e)
+                        .unwrap()
+                        .display()
+                        .unwrap();
+                    if let Some(sort_field) = sort_fields.last_mut() {
+                        if xml_pos.in_primary_field {
+                            sort_field.field_reference = field_reference;
+                        } else if xml_pos.in_summary_field {
+                            sort_field.summary_field = field_reference;
+                        }
+                    }
+                }
+                b"ValueListReference" => {
+                    if let Some(sort_field) = sort_fields.last_mut() {
+                        sort_field.value_list = get_attribute(&e, "name").unwrap_or_default();
+                    }
+                }
+                b"LanguageReference" => {
+                    if let Some(sort_field) = sort_fields.last_mut() {
+                        sort_field.language = get_attribute(&e, "name").unwrap_or_default();
+                    }
+                }
+                _ => {}
+            },
+            Ok(Event::End(e)) => match e.name().as_ref() {
+                b"PrimaryField" => xml_pos.in_primary_field = false,
+                b"SummaryField" => xml_pos.in_summary_field = false,
+                _ => {}
+            },
+            _ => {}
+        }
+        buf.clear()
+    }
+
+    if restore {
+        params.push("Restore".to_string());
+    }
+    if keep_sorted {
+        params.push("Keep records in sorted order".to_string());
+    }
+    if !sort_fields.is_empty() {
+        let sort_order = sort_fields
+            .iter()
+            .map(|sort_field| {
+                let mut options = vec![sort_field.order.clone()];
+                if !sort_field.value_list.is_empty() {
+                    options.push(format!("Value list: {}", sort_field.value_list));
+                }
+                if !sort_field.summary_field.is_empty() {
+                    options.push(format!("By summary: {}", sort_field.summary_field));
+                }
+                if !sort_field.language.is_empty() {
+                    options.push(format!("Language: {}", sort_field.language));
+                }
+                format!("{} ({})", sort_field.field_reference, options.join(", "))
+            })
+            .collect::<Vec<_>>();
+        params.push(format!("Sort order: {}", sort_order.join(", ")));
+    }
+
+    if name.is_empty() {
+        None
+    } else {
+        Some(format!("{} [ {} ]", name, params.join(" ; ")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sanitize_without_restore() {
+        let xml_input = "
+        <Step id=\"39\" name=\"Sortieren\" enable=\"True\">
+            <Options>128</Options>
+            <ParameterValues membercount=\"2\">
+                <Parameter type=\"Boolean\">
+                    <Boolean type=\"Mit Dialog\" id=\"128\" value=\"False\"></Boolean>
+                </Parameter>
+                <Parameter type=\"Restore\">
+                    <Restore value=\"False\"></Restore>
+                </Parameter>
+            </ParameterValues>
+        </Step>
+        ";
+
+        let expected_output = Some("Sortieren [ Mit Dialog: OFF ]".to_string());
+        assert_eq!(sanitize(xml_input.trim()), expected_output);
+    }
+
+    #[test]
+    fn test_sanitize_sort_order() {
+        let xml_input = "
+        <Step id=\"39\" name=\"Sortieren\" enable=\"True\">
+            <Options>33587328</Options>
+            <ParameterValues membercount=\"3\">
+                <Parameter type=\"Boolean\">
+                    <Boolean type=\"Mit Dialog\" id=\"128\" value=\"False\"></Boolean>
+                </Parameter>
+                <Parameter type=\"Restore\">
+                    <Restore value=\"True\"></Restore>
+                </Parameter>
+                <Parameter type=\"SortSpecification\">
+                    <SortSpecification value=\"True\" maintain=\"True\">
+                        <SortList membercount=\"3\">
+                            <Sort type=\"Descending\">
+                                <PrimaryField>
+                                    <FieldReference id=\"23\" name=\"IsMarked\">
+                                        <TableOccurrenceReference id=\"1065113\" name=\"_Syntax\"></TableOccurrenceReference>
+                                    </FieldReference>
+                                </PrimaryField>
+                            </Sort>
+                            <Sort type=\"Custom\">
+                                <PrimaryField>
+                                    <FieldReference id=\"3\" name=\"ColorName\">
+                                        <TableOccurrenceReference id=\"1065113\" name=\"_Syntax\"></TableOccurrenceReference>
+                                    </FieldReference>
+                                </PrimaryField>
+                                <ValueListReference id=\"1\" name=\"Colors\"></ValueListReference>
+                            </Sort>
+                            <Sort type=\"Ascending\">
+                                <PrimaryField>
+                                    <FieldReference id=\"2\" name=\"Command\">
+                                        <TableOccurrenceReference id=\"1065117\" name=\"Syntax.GUISyntax\"></TableOccurrenceReference>
+                                    </FieldReference>
+                                </PrimaryField>
+                                <SummaryField>
+                                    <FieldReference id=\"11\" name=\"_s1\">
+                                        <TableOccurrenceReference id=\"1065117\" name=\"Syntax.GUISyntax\"></TableOccurrenceReference>
+                                    </FieldReference>
+                                </SummaryField>
+                                <LanguageReference name=\"German\" id=\"25\"></LanguageReference>
+                            </Sort>
+                        </SortList>
+                    </SortSpecification>
+                </Parameter>
+            </ParameterValues>
+        </Step>
+        ";
+
+        let expected_output = Some("Sortieren [ Mit Dialog: OFF ; Restore ; Keep records in sorted order ; Sort order: _Syntax::IsMarked (Descending), _Syntax::ColorName (Custom, Value list: Colors), Syntax.GUISyntax::Command (Ascending, By summary: Syntax.GUISyntax::_s1, Language: German) ]".to_string());
+        assert_eq!(sanitize(xml_input.trim()), expected_output);
+    }
+}
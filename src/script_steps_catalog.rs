@@ -1,6 +1,10 @@
-use crate::script_steps::constants::{id_to_script_step, ScriptStep};
+use crate::calculations::base_table_resolver::BaseTableResolver;
+use crate::references::{describe_location, References};
+use crate::script_steps::constants::{id_to_script_step, step_name, ScriptStep};
+use crate::script_steps::perform_script::performed_scripts;
 use crate::script_steps::sanitizer::sanitize;
-use crate::{escape_filename, join_scope_id_and_name};
+use crate::where_used::scripts::ScriptCall;
+use crate::{escape_filename, join_scope_id_and_name, Settings};
 use quick_xml::events::{BytesStart, Event};
 use quick_xml::reader::Reader;
 use std::collections::HashMap;
@@ -27,6 +31,8 @@ struct ScriptInfo {
 #[derive(Debug, Default)]
 struct ScriptStepInfo {
     id: String,
+    name: String,
+    number: usize,
     content: String,
     indent_level_current: usize,
     indent_level_next: usize,
@@ -38,7 +44,9 @@ pub fn xml_explode_script_catalog<R: Read + BufRead>(
     out_dir_path: &Path,
     fm_file_name: &str,
     script_id_path_map: &HashMap<String, Vec<String>>,
-) {
+    base_table_resolver: &BaseTableResolver,
+    settings: &Settings,
+) -> References {
     let scripts_xml_out_dir_path = out_dir_path.join("scripts").join(fm_file_name);
     let scripts_text_out_dir_path = out_dir_path.join("scripts_sanitized").join(fm_file_name);
     initialize_out_dir(&scripts_xml_out_dir_path);
@@ -49,6 +57,8 @@ pub fn xml_explode_script_catalog<R: Read + BufRead>(
     let mut in_step = false;
     let mut step_info = ScriptStepInfo::default();
 
+    let mut references = References::default();
+
     let mut depth = 1;
     let mut buf = Vec::new();
     loop {
@@ -66,6 +76,7 @@ pub fn xml_explode_script_catalog<R: Read + BufRead>(
                     script_info.name.clear();
                     script_info.xml.clear();
                     script_info.text.clear();
+                    step_info.number = 0;
                     step_info.content.clear();
                 } else if depth == 3 && e.name().as_ref() == b"ScriptReference" {
                     for attr in get_attributes(&e).unwrap() {
@@ -84,18 +95,22 @@ pub fn xml_explode_script_catalog<R: Read + BufRead>(
                     };
                 } else if depth == 4 && local_name_to_string(e.name().as_ref()) == "Step" {
                     in_step = true;
+                    step_info.number += 1;
                     step_info.indent_level_current = step_info.indent_level_next;
                     step_info.id = get_attribute(&e, "id").unwrap().to_string();
+                    step_info.name = get_attribute(&e, "name").unwrap_or_default();
 
                     if get_attribute(&e, "enable").unwrap_or("True".to_string()) == "True" {
                         match id_to_script_step(&step_info.id) {
-                            ScriptStep::IfStart | ScriptStep::LoopStart => {
-                                step_info.indent_level_next += 1
-                            }
+                            ScriptStep::IfStart
+                            | ScriptStep::LoopStart
+                            | ScriptStep::OpenTransaction => step_info.indent_level_next += 1,
                             ScriptStep::IfElse | ScriptStep::Else => {
                                 step_info.indent_level_current -= 1
                             }
-                            ScriptStep::IfEnd | ScriptStep::LoopEnd => {
+                            ScriptStep::IfEnd
+                            | ScriptStep::LoopEnd
+                            | ScriptStep::CommitTransaction => {
                                 step_info.indent_level_current -= 1;
                                 step_info.indent_level_next = step_info.indent_level_current;
                             }
@@ -124,6 +139,9 @@ pub fn xml_explode_script_catalog<R: Read + BufRead>(
 
                 if depth == 1 && e.name().as_ref() == b"Script" {
                     write_script_to_file(out_dir_path, fm_file_name, &script_info);
+                    references
+                        .scripts
+                        .push((script_info.id.to_string(), script_info.name.to_string()));
                     script_info.id.clear();
                     script_info.name.clear();
                     script_info.text.clear();
@@ -140,7 +158,48 @@ pub fn xml_explode_script_catalog<R: Read + BufRead>(
 
                 if depth == 3 && local_name_to_string(e.name().as_ref()) == "Step" {
                     let is_comment = id_to_script_step(&step_info.id) == ScriptStep::Comment;
-                    match sanitize(&step_info.id, &step_info.content) {
+                    let localized_step_name =
+                        step_name(&step_info.id, &step_info.name, settings.step_language);
+                    references.collect(
+                        "Script",
+                        &script_info.id,
+                        &script_info.name,
+                        &script_info.path,
+                        Some((step_info.number, localized_step_name)),
+                        &step_info.content,
+                    );
+                    match id_to_script_step(&step_info.id) {
+                        ScriptStep::PerformScript
+                        | ScriptStep::PerformScriptOnServer
+                        | ScriptStep::PerformScriptOnServerWithCallback => {
+                            for performed_script in performed_scripts(&step_info.content)
+                                .into_iter()
+                                .filter(|performed_script| performed_script.is_by_name())
+                            {
+                                references.script_calls.push(ScriptCall {
+                                    script_id: String::new(),
+                                    script_name: performed_script.reference,
+                                    caller_type: "Script".to_string(),
+                                    caller_id: script_info.id.to_string(),
+                                    step: Some(step_info.number),
+                                    step_name: localized_step_name.to_string(),
+                                    location: describe_location(
+                                        "Script",
+                                        &join_scope_id_and_name(&script_info.id, &script_info.name),
+                                        &script_info.path,
+                                        Some(step_info.number),
+                                    ),
+                                });
+                            }
+                        }
+                        _ => {}
+                    }
+                    let sanitized = match settings.annotate_base_tables {
+                        true => base_table_resolver
+                            .apply(|| sanitize(&step_info.id, &step_info.content, settings)),
+                        false => sanitize(&step_info.id, &step_info.content, settings),
+                    };
+                    match sanitized {
                         None => {}
                         Some(text) => {
                             let mut first_line_done = false;
@@ -193,6 +252,8 @@ pub fn xml_explode_script_catalog<R: Read + BufRead>(
 
         buf.clear()
     }
+
+    references
 }
 
 fn write_script_to_file(dir_path: &Path, fm_file_name: &str, script: &ScriptInfo) {
@@ -1,4 +1,6 @@
-use crate::{escape_filename, join_scope_id_and_name};
+use crate::calculations::calculation::{Calculation, CalculationFormat};
+use crate::references::References;
+use crate::{escape_filename, join_scope_id_and_name, Settings};
 use quick_xml::events::{BytesStart, Event};
 use quick_xml::reader::Reader;
 use std::collections::HashMap;
@@ -8,9 +10,70 @@ use std::path::Path;
 use crate::utils::attributes::{get_attribute, get_attributes};
 use crate::utils::xml_utils::{
     cdata_element_to_string, end_element_to_string, local_name_to_string, start_element_to_string,
-    text_element_to_string,
+    text_element_to_string, text_to_string,
 };
-use crate::utils::{initialize_out_dir, write_xml_file, Entity};
+use crate::utils::{initialize_out_dir, write_text_file, write_xml_file, Entity};
+
+#[derive(Debug, Default)]
+struct FieldInfo {
+    id: String,
+    name: String,
+    field_type: String,
+    datatype: String,
+    comment: String,
+    auto_enter: Vec<String>,
+    validation: Vec<String>,
+    validation_options: Vec<String>,
+    storage: Vec<String>,
+    summary: String,
+    /// Label and text of the field calculation, auto-enter and validation calculations.
+    calculations: Vec<(&'static str, String)>,
+}
+
+impl FieldInfo {
+    fn display(&self) -> String {
+        let mut params = vec![self.field_type.to_string(), self.datatype.to_string()];
+        if !self.summary.is_empty() {
+            params.push(format!("Summary: {}", self.summary));
+        }
+        if !self.auto_enter.is_empty() {
+            params.push(format!("Auto-enter: {}", self.auto_enter.join(", ")));
+        }
+        if !self.validation.is_empty() {
+            params.push(format!(
+                "Validation: {} ({})",
+                self.validation.join(", "),
+                self.validation_options.join(", ")
+            ));
+        }
+        if !self.storage.is_empty() {
+            params.push(format!("Storage: {}", self.storage.join(", ")));
+        }
+        if !self.comment.is_empty() {
+            params.push(format!(
+                "Comment: {}",
+                self.comment
+                    .split(['\r', '\n'])
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            ));
+        }
+
+        let mut lines = vec![format!(
+            "{} [ {} ]",
+            join_scope_id_and_name(&self.id, &self.name),
+            params.join(" ; ")
+        )];
+        for (label, calculation) in &self.calculations {
+            lines.push(format!("\t{}:", label));
+            for line in calculation.split(['\r', '\n']) {
+                lines.push(format!("\t\t{}", line));
+            }
+        }
+
+        lines.join("\n")
+    }
+}
 
 pub fn xml_explode_table_catalog<R: Read + BufRead>(
     reader: &mut Reader<R>,
@@ -18,12 +81,19 @@ pub fn xml_explode_table_catalog<R: Read + BufRead>(
     out_dir_path: &Path,
     fm_file_name: &str,
     table_name_id_map: &HashMap<String, String>,
-) {
-    let out_dir_path = out_dir_path.join("tables").join(fm_file_name);
-    initialize_out_dir(&out_dir_path);
+    settings: &Settings,
+) -> References {
+    let xml_out_dir_path = out_dir_path.join("tables").join(fm_file_name);
+    let text_out_dir_path = out_dir_path.join("tables_sanitized").join(fm_file_name);
+    initialize_out_dir(&xml_out_dir_path);
+    if settings.sanitize_tables {
+        initialize_out_dir(&text_out_dir_path);
+    }
 
     let mut table_info = Entity::default();
 
+    let mut references = References::default();
+
     let mut depth = 1;
     let mut buf = Vec::new();
     loop {
@@ -79,7 +149,22 @@ pub fn xml_explode_table_catalog<R: Read + BufRead>(
                     .push_str(end_element_to_string(&e).as_str());
 
                 if depth == 1 && local_name_to_string(e.name().as_ref()) == "FieldCatalog" {
-                    write_table_to_file(&out_dir_path, &table_info);
+                    references.collect(
+                        "Table",
+                        &table_info.id,
+                        &table_info.name,
+                        &[],
+                        None,
+                        &table_info.content,
+                    );
+                    write_table_to_file(&xml_out_dir_path, &table_info);
+                    if settings.sanitize_tables {
+                        write_table_to_text_file(
+                            &text_out_dir_path,
+                            &table_info,
+                            &sanitize_fields(&table_info.content, settings),
+                        );
+                    }
                     table_info.clear();
                 }
             }
@@ -103,11 +188,236 @@ pub fn xml_explode_table_catalog<R: Read + BufRead>(
 
         buf.clear()
     }
+
+    references
 }
 
 fn write_table_to_file(output_dir: &Path, table: &Entity) {
-    let table_filename = join_scope_id_and_name(table.id.as_str(), table.name.as_str());
-    let table_filename = escape_filename(&table_filename);
-    let output_file_path = output_dir.join(format!("{}.xml", table_filename));
+    let output_file_path = output_dir.join(format!("{}.xml", table_filename(table)));
     write_xml_file(&output_file_path, &table.content, 4);
 }
+
+fn write_table_to_text_file(output_dir: &Path, table: &Entity, content: &str) {
+    let output_file_path = output_dir.join(format!("{}.txt", table_filename(table)));
+    write_text_file(&output_file_path, content);
+}
+
+fn table_filename(table: &Entity) -> String {
+    let table_filename = join_scope_id_and_name(table.id.as_str(), table.name.as_str());
+    escape_filename(&table_filename)
+}
+
+/// Renders every field of a `FieldCatalog` as one line, followed by its calculations.
+fn sanitize_fields(xml: &str, settings: &Settings) -> String {
+    let mut fields: Vec<FieldInfo> = Vec::new();
+    let mut parents: Vec<String> = Vec::new();
+
+    let mut reader = Reader::from_str(xml);
+    let mut buf = Vec::new();
+    loop {
+        let parent = parents.last().cloned().unwrap_or_default();
+        match reader.read_event_into(&mut buf) {
+            Err(_) | Ok(Event::Eof) => break,
+            Ok(Event::Start(e)) if e.name().as_ref() == b"Calculation" => {
+                let Ok(calculation) = Calculation::parse(&mut reader, &e) else {
+                    continue;
+                };
+                let Some(field) = fields.last_mut() else {
+                    continue;
+                };
+                let label = match parent.as_str() {
+                    "Calculated" => "Auto-enter calculation",
+                    "Validation" => {
+                        field.validation.push("calculation".to_string());
+                        "Validation calculation"
+                    }
+                    _ => "Calculation",
+                };
+                field
+                    .calculations
+                    .push((label, calculation.render(CalculationFormat::new(settings))));
+            }
+            Ok(Event::Start(e)) => {
+                read_field_element(&mut fields, &parent, &e);
+                parents.push(local_name_to_string(e.name().as_ref()));
+            }
+            Ok(Event::Empty(e)) => read_field_element(&mut fields, &parent, &e),
+            Ok(Event::Text(e)) => match (parent.as_str(), fields.last_mut()) {
+                ("Strict", Some(field)) => field
+                    .validation
+                    .push(format!("strict: {}", text_to_string(&e))),
+                ("ConstantData", Some(field)) => {
+                    if let Some(auto_enter) = field.auto_enter.first_mut() {
+                        auto_enter.push_str(&format!(" \"{}\"", text_to_string(&e)));
+                    }
+                }
+                _ => {}
+            },
+            Ok(Event::End(_)) => {
+                parents.pop();
+            }
+            _ => {}
+        }
+        buf.clear()
+    }
+
+    fields
+        .iter()
+        .map(|field| field.display())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn read_field_element(fields: &mut Vec<FieldInfo>, parent: &str, e: &BytesStart) {
+    let attribute = |name: &str| get_attribute(e, name).unwrap_or_default();
+
+    if e.name().as_ref() == b"Field" && parent == "ObjectList" {
+        fields.push(FieldInfo {
+            id: attribute("id"),
+            name: attribute("name"),
+            field_type: attribute("fieldtype"),
+            datatype: attribute("datatype"),
+            comment: attribute("comment"),
+            ..Default::default()
+        });
+        return;
+    }
+
+    let Some(field) = fields.last_mut() else {
+        return;
+    };
+    match e.name().as_ref() {
+        b"AutoEnter" => {
+            match attribute("type").as_str() {
+                "" => {}
+                "SerialNumber" => field.auto_enter.push("Serial number".to_string()),
+                "Calculated" => field.auto_enter.push("Calculation".to_string()),
+                "ConstantData" => field.auto_enter.push("Data".to_string()),
+                auto_enter_type => field.auto_enter.push(auto_enter_type.to_string()),
+            }
+            if attribute("overwriteExisting") == "False" {
+                field
+                    .auto_enter
+                    .push("do not replace existing value".to_string());
+            }
+            if attribute("alwaysEvaluate") == "True" {
+                field.auto_enter.push("always evaluate".to_string());
+            }
+            if attribute("prohibitModification") == "True" {
+                field.auto_enter.push("prohibit modification".to_string());
+            }
+        }
+        b"SerialNumber" => {
+            if let Some(auto_enter) = field.auto_enter.first_mut() {
+                auto_enter.push_str(&format!(
+                    " (increment: {}, generate: {})",
+                    attribute("increment"),
+                    attribute("generate")
+                ));
+            }
+        }
+        b"Validation" => {
+            field.validation_options = vec![
+                match attribute("type").as_str() {
+                    "OnlyDuringDataEntry" => "only during data entry".to_string(),
+                    validation_type => validation_type.to_lowercase(),
+                },
+                match attribute("allowOverride").as_str() {
+                    "False" => "no override".to_string(),
+                    _ => "allow override".to_string(),
+                },
+            ];
+            for (name, constraint) in [
+                ("notEmpty", "not empty"),
+                ("unique", "unique"),
+                ("existing", "existing"),
+            ] {
+                if attribute(name) == "True" {
+                    field.validation.push(constraint.to_string());
+                }
+            }
+        }
+        b"ValueListReference" if parent == "Validation" => field
+            .validation
+            .push(format!("member of value list: {}", attribute("name"))),
+        b"Storage" => {
+            if attribute("global") == "True" {
+                field.storage.push("global".to_string());
+            }
+            if attribute("storeCalculationResults") == "False" {
+                field.storage.push("unstored".to_string());
+            }
+            if attribute("autoIndex") == "True" {
+                field.storage.push("auto index".to_string());
+            }
+            match attribute("maxRepetitions").as_str() {
+                "" | "1" => {}
+                repetitions => field.storage.push(format!("repetitions: {}", repetitions)),
+            }
+        }
+        b"SummaryInfo" => field.summary = attribute("operation"),
+        b"FieldReference" if parent == "SummaryField" => {
+            field.summary = format!("{} of {}", field.summary, attribute("name"))
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sanitize_fields() {
+        let xml_input = "
+        <FieldCatalog>
+            <BaseTableReference id=\"130\" name=\"Customers\"></BaseTableReference>
+            <ObjectList membercount=\"4\">
+                <Field DefaultStyle=\"\" id=\"1\" name=\"ID\" fieldtype=\"Normal\" datatype=\"Number\" comment=\"Primary key\">
+                    <AutoEnter type=\"SerialNumber\" prohibitModification=\"True\">
+                        <SerialNumber increment=\"1\" generate=\"OnCreation\"></SerialNumber>
+                    </AutoEnter>
+                    <Validation type=\"Always\" allowOverride=\"False\" notEmpty=\"True\" unique=\"True\" existing=\"False\"></Validation>
+                    <Storage autoIndex=\"True\" global=\"False\" maxRepetitions=\"1\"></Storage>
+                </Field>
+                <Field DefaultStyle=\"\" id=\"2\" name=\"Created\" fieldtype=\"Normal\" datatype=\"Date\" comment=\"\">
+                    <AutoEnter type=\"ConstantData\" prohibitModification=\"False\">
+                        <ConstantData>1.1.2000</ConstantData>
+                    </AutoEnter>
+                    <Validation type=\"OnlyDuringDataEntry\" allowOverride=\"True\" notEmpty=\"False\" unique=\"False\" existing=\"False\">
+                        <Strict>FourDigitYear</Strict>
+                    </Validation>
+                    <Storage global=\"False\" maxRepetitions=\"1\"></Storage>
+                </Field>
+                <Field DefaultStyle=\"\" id=\"3\" name=\"Label\" fieldtype=\"Calculated\" datatype=\"Text\" comment=\"\">
+                    <AutoEnter alwaysEvaluate=\"False\"></AutoEnter>
+                    <Storage storeCalculationResults=\"False\" global=\"False\" maxRepetitions=\"2\"></Storage>
+                    <Calculation>
+                        <Text><![CDATA[Let ( x = ID ;\r\"#\" & x )]]></Text>
+                    </Calculation>
+                </Field>
+                <Field DefaultStyle=\"\" id=\"4\" name=\"Count\" fieldtype=\"Summary\" datatype=\"Number\" comment=\"\">
+                    <SummaryInfo restartEachGroup=\"False\" summarizeRepetition=\"Together\" operation=\"Count\">
+                        <SummaryField>
+                            <FieldReference id=\"1\" name=\"ID\">
+                                <BaseTableReference id=\"130\" name=\"Customers\"></BaseTableReference>
+                            </FieldReference>
+                        </SummaryField>
+                    </SummaryInfo>
+                </Field>
+            </ObjectList>
+        </FieldCatalog>
+        ";
+
+        assert_eq!(
+            sanitize_fields(xml_input, &Settings::default()),
+            "ID - ID 1 [ Normal ; Number ; Auto-enter: Serial number (increment: 1, generate: OnCreation), prohibit modification ; Validation: not empty, unique (always, no override) ; Storage: auto index ; Comment: Primary key ]
+Created - ID 2 [ Normal ; Date ; Auto-enter: Data \"1.1.2000\" ; Validation: strict: FourDigitYear (only during data entry, allow override) ]
+Label - ID 3 [ Calculated ; Text ; Storage: unstored, repetitions: 2 ]
+\tCalculation:
+\t\tLet ( x = ID ;
+\t\t\"#\" & x )
+Count - ID 4 [ Summary ; Number ; Summary: Count of ID ]"
+        );
+    }
+}
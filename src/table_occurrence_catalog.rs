@@ -3,18 +3,30 @@ use quick_xml::reader::Reader;
 use std::io::{BufRead, Read};
 use std::path::Path;
 
+use crate::utils::attributes::get_attribute;
 use crate::utils::xml_utils::skip_element;
-use crate::utils::{initialize_out_dir, write_xml_element_to_file};
+use crate::utils::{initialize_out_dir, write_entity_to_file, Entity};
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct TableOccurrence {
+    pub id: String,
+    pub name: String,
+    pub base_table_id: String,
+    pub base_table_name: String,
+    pub is_external: bool,
+}
 
 pub fn xml_explode_table_occurrence_catalog<R: Read + BufRead>(
     reader: &mut Reader<R>,
     _: &BytesStart,
     out_dir_path: &Path,
     fm_file_name: &str,
-) {
+) -> Vec<TableOccurrence> {
     let out_dir_path = out_dir_path.join("table_occurrences").join(fm_file_name);
     initialize_out_dir(&out_dir_path);
 
+    let mut table_occurrences: Vec<TableOccurrence> = Vec::new();
+
     let mut depth = 1;
     let mut buf = Vec::new();
     loop {
@@ -28,7 +40,18 @@ pub fn xml_explode_table_occurrence_catalog<R: Read + BufRead>(
                 depth += 1;
                 if depth == 2 {
                     if e.name().as_ref() == b"TableOccurrence" {
-                        write_xml_element_to_file(reader, &e, &out_dir_path, 4);
+                        let mut entity = Entity::default();
+                        entity.read_xml_element(reader, &e);
+                        write_entity_to_file(&out_dir_path, &entity, 4);
+                        let (base_table_id, base_table_name) = base_table(&entity.content);
+                        table_occurrences.push(TableOccurrence {
+                            id: entity.id,
+                            name: entity.name,
+                            base_table_id,
+                            base_table_name,
+                            is_external: get_attribute(&e, "type").unwrap_or_default()
+                                == "External",
+                        });
                         depth -= 1;
                     } else {
                         skip_element(reader, &e);
@@ -47,4 +70,26 @@ pub fn xml_explode_table_occurrence_catalog<R: Read + BufRead>(
 
         buf.clear()
     }
+
+    table_occurrences
+}
+
+fn base_table(table_occurrence: &str) -> (String, String) {
+    let mut reader = Reader::from_str(table_occurrence);
+    let mut buf = Vec::new();
+    loop {
+        match reader.read_event_into(&mut buf) {
+            Err(_) | Ok(Event::Eof) => break,
+            Ok(Event::Start(e)) if e.name().as_ref() == b"BaseTableReference" => {
+                return (
+                    get_attribute(&e, "id").unwrap_or_default(),
+                    get_attribute(&e, "name").unwrap_or_default(),
+                );
+            }
+            _ => {}
+        }
+        buf.clear()
+    }
+
+    (String::new(), String::new())
 }
@@ -3,18 +3,21 @@ use quick_xml::reader::Reader;
 use std::io::{BufRead, Read};
 use std::path::Path;
 
+use crate::references::References;
 use crate::utils::xml_utils::skip_element;
-use crate::utils::{initialize_out_dir, write_xml_element_to_file};
+use crate::utils::{initialize_out_dir, write_entity_to_file, Entity};
 
 pub fn xml_explode_value_list_catalog<R: Read + BufRead>(
     reader: &mut Reader<R>,
     _: &BytesStart,
     out_dir_path: &Path,
     fm_file_name: &str,
-) {
+) -> References {
     let out_dir_path = out_dir_path.join("value_lists").join(fm_file_name);
     initialize_out_dir(&out_dir_path);
 
+    let mut references = References::default();
+
     let mut depth = 1;
     let mut buf = Vec::new();
     loop {
@@ -28,7 +31,17 @@ pub fn xml_explode_value_list_catalog<R: Read + BufRead>(
                 depth += 1;
                 if depth == 2 {
                     if e.name().as_ref() == b"ValueList" {
-                        write_xml_element_to_file(reader, &e, &out_dir_path, 4);
+                        let mut value_list = Entity::default();
+                        value_list.read_xml_element(reader, &e);
+                        references.collect(
+                            "Value list",
+                            &value_list.id,
+                            &value_list.name,
+                            &[],
+                            None,
+                            &value_list.content,
+                        );
+                        write_entity_to_file(&out_dir_path, &value_list, 4);
                         depth -= 1;
                     } else {
                         skip_element(reader, &e);
@@ -47,4 +60,6 @@ pub fn xml_explode_value_list_catalog<R: Read + BufRead>(
 
         buf.clear()
     }
+
+    references
 }
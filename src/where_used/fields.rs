@@ -0,0 +1,209 @@
+use crate::calculations::base_table_resolver::BaseTableResolver;
+use crate::calculations::field_reference::FieldReference;
+use crate::utils::attributes::get_attribute;
+use crate::utils::{create_dir, initialize_out_dir, write_text_file};
+use crate::{escape_filename, join_scope_id_and_name};
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldUse {
+    pub field_reference: FieldReference,
+    pub location: String,
+}
+
+/// Returns all field references in the given XML together with the field definition they are
+/// part of, e.g. the calculation of another field.
+pub fn find_field_references(xml: &str) -> Vec<(FieldReference, Option<String>)> {
+    let mut references: Vec<(FieldReference, Option<String>)> = Vec::new();
+    let mut fields: Vec<Option<String>> = Vec::new();
+
+    let mut reader = Reader::from_str(xml);
+    let mut buf = Vec::new();
+    loop {
+        match reader.read_event_into(&mut buf) {
+            Err(_) | Ok(Event::Eof) => break,
+            Ok(Event::Start(e)) => match e.name().as_ref() {
+                b"FieldReference" => {
+                    let field_reference = FieldReference::from_xml(&mut reader, &e).unwrap();
+                    let field = fields.iter().rev().flatten().next().cloned();
+                    references.push((field_reference, field));
+                }
+                b"Field" => {
+                    fields.push(match (get_attribute(&e, "id"), get_attribute(&e, "name")) {
+                        (Some(id), Some(name)) => Some(join_scope_id_and_name(&id, &name)),
+                        _ => None,
+                    })
+                }
+                _ => {}
+            },
+            Ok(Event::End(e)) if e.name().as_ref() == b"Field" => {
+                fields.pop();
+            }
+            _ => {}
+        }
+        buf.clear()
+    }
+
+    references
+}
+
+pub fn write_field_index(
+    out_dir_path: &Path,
+    fm_file_name: &str,
+    field_uses: &[FieldUse],
+    base_table_resolver: &BaseTableResolver,
+) {
+    let out_dir_path = out_dir_path
+        .join("where_used")
+        .join("fields")
+        .join(fm_file_name);
+    initialize_out_dir(&out_dir_path);
+
+    for ((table, field), locations) in index_field_uses(field_uses, base_table_resolver) {
+        let table_dir_path = out_dir_path.join(escape_filename(&table));
+        create_dir(&table_dir_path);
+        write_text_file(
+            &table_dir_path.join(format!("{}.txt", escape_filename(&field))),
+            &locations.join("\n"),
+        );
+    }
+}
+
+/// Groups the uses by base table and field id, so that all table occurrences of a table end
+/// up in the same file.
+fn index_field_uses(
+    field_uses: &[FieldUse],
+    base_table_resolver: &BaseTableResolver,
+) -> BTreeMap<(String, String), Vec<String>> {
+    let mut field_names: BTreeMap<(&str, &str), &str> = BTreeMap::new();
+    let mut index: BTreeMap<(String, String), Vec<String>> = BTreeMap::new();
+
+    for field_use in field_uses {
+        let reference = &field_use.field_reference;
+        let Some((table_id, table_name)) =
+            base_table_resolver.base_table(&reference.table_occurrence_id)
+        else {
+            continue;
+        };
+
+        // the name of the first reference wins, so that all uses end up in one file
+        let field_name = field_names
+            .entry((table_id, reference.id.as_str()))
+            .or_insert(reference.name.as_str());
+
+        let locations = index
+            .entry((
+                join_scope_id_and_name(table_id, table_name),
+                join_scope_id_and_name(&reference.id, field_name),
+            ))
+            .or_default();
+        if !locations.contains(&field_use.location) {
+            locations.push(field_use.location.to_string());
+        }
+    }
+
+    index
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::table_occurrence_catalog::TableOccurrence;
+
+    #[test]
+    fn test_find_field_references() {
+        let xml_input = "
+        <Field id=\"3\" name=\"FullName\" fieldtype=\"Calculated\" datatype=\"Text\">
+            <Calculation table=\"Customers\">
+                <Text><![CDATA[FirstName & \" \" & Invoices::Number]]></Text>
+                <ChunkList>
+                    <Chunk type=\"FieldRef\">
+                        <FieldReference id=\"1\" name=\"FirstName\">
+                            <TableOccurrenceReference id=\"1065089\" name=\"Customers\"></TableOccurrenceReference>
+                        </FieldReference>
+                    </Chunk>
+                    <Chunk type=\"NoRef\"> &amp; &quot; &quot; &amp; </Chunk>
+                    <Chunk type=\"FieldRef\">
+                        <FieldReference id=\"2\" name=\"Number\">
+                            <TableOccurrenceReference id=\"1065091\" name=\"Invoices\"></TableOccurrenceReference>
+                        </FieldReference>
+                    </Chunk>
+                </ChunkList>
+            </Calculation>
+        </Field>
+        <Field>
+            <FieldReference id=\"4\" name=\"City\">
+                <TableOccurrenceReference id=\"1065089\" name=\"Customers\"></TableOccurrenceReference>
+            </FieldReference>
+        </Field>
+        ";
+
+        let references = find_field_references(xml_input)
+            .into_iter()
+            .map(|(reference, field)| {
+                (
+                    format!("{}::{}", reference.table_occurrence_name, reference.name),
+                    field,
+                )
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            references,
+            vec![
+                (
+                    "Customers::FirstName".to_string(),
+                    Some("FullName - ID 3".to_string())
+                ),
+                (
+                    "Invoices::Number".to_string(),
+                    Some("FullName - ID 3".to_string())
+                ),
+                ("Customers::City".to_string(), None),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_index_field_uses() {
+        let resolver = BaseTableResolver::new(&["1065089", "1065090"].map(|id| TableOccurrence {
+            id: id.to_string(),
+            base_table_id: "130".to_string(),
+            base_table_name: "Customers".to_string(),
+            ..Default::default()
+        }));
+        let field_use = |table_occurrence_id: &str, location: &str| FieldUse {
+            field_reference: FieldReference {
+                id: "1".to_string(),
+                name: "Name".to_string(),
+                table_occurrence_id: table_occurrence_id.to_string(),
+                ..Default::default()
+            },
+            location: location.to_string(),
+        };
+
+        let index = index_field_uses(
+            &[
+                field_use("1065089", "Script: Start - ID 1 ; Step: 2"),
+                field_use("1065090", "Layout: Customers - ID 3"),
+                field_use("1065089", "Script: Start - ID 1 ; Step: 2"),
+                field_use("1065999", "Layout: External - ID 4"),
+            ],
+            &resolver,
+        );
+
+        assert_eq!(
+            index,
+            BTreeMap::from([(
+                ("Customers - ID 130".to_string(), "Name - ID 1".to_string()),
+                vec![
+                    "Script: Start - ID 1 ; Step: 2".to_string(),
+                    "Layout: Customers - ID 3".to_string()
+                ]
+            )])
+        );
+    }
+}
@@ -0,0 +1,242 @@
+use crate::utils::attributes::get_attribute;
+use crate::utils::{create_dir, initialize_out_dir, write_text_file};
+use crate::{escape_filename, join_scope_id_and_name};
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScriptCall {
+    /// Empty for scripts called by name, `script_name` then holds the calculation.
+    pub script_id: String,
+    pub script_name: String,
+    pub caller_type: String,
+    pub caller_id: String,
+    pub step: Option<usize>,
+    pub step_name: String,
+    pub location: String,
+}
+
+/// Returns id and name of all called scripts in the given XML, together with the script trigger
+/// or button they are attached to.
+pub fn find_script_references(xml: &str) -> Vec<(String, String, Option<String>)> {
+    let mut references: Vec<(String, String, Option<String>)> = Vec::new();
+    let mut trigger: Option<String> = None;
+    let mut in_button = 0;
+
+    let mut reader = Reader::from_str(xml);
+    let mut buf = Vec::new();
+    loop {
+        match reader.read_event_into(&mut buf) {
+            Err(_) | Ok(Event::Eof) => break,
+            // self-closing triggers and actions have no script reference and no end event
+            Ok(Event::Start(e)) if e.name().as_ref() == b"ScriptTrigger" => {
+                trigger = get_attribute(&e, "action")
+            }
+            Ok(Event::Start(e)) if e.name().as_ref() == b"action" => in_button += 1,
+            Ok(Event::Start(e)) | Ok(Event::Empty(e))
+                if e.name().as_ref() == b"ScriptReference" =>
+            {
+                let id = get_attribute(&e, "id").unwrap_or_default();
+                let name = get_attribute(&e, "name").unwrap_or_default();
+                if id == "0" || name.is_empty() {
+                    continue;
+                }
+
+                let context = match (&trigger, in_button) {
+                    (Some(trigger), _) => Some(format!("Trigger: {}", trigger)),
+                    (None, 1..) => Some("Button".to_string()),
+                    _ => None,
+                };
+                references.push((id, name, context));
+            }
+            Ok(Event::End(e)) => match e.name().as_ref() {
+                b"ScriptTrigger" => trigger = None,
+                b"action" => in_button -= 1,
+                _ => {}
+            },
+            _ => {}
+        }
+        buf.clear()
+    }
+
+    references
+}
+
+pub fn write_script_index(
+    out_dir_path: &Path,
+    fm_file_name: &str,
+    scripts: &[(String, String)],
+    script_calls: &[ScriptCall],
+    script_id_path_map: &HashMap<String, Vec<String>>,
+) {
+    let out_dir_path = out_dir_path
+        .join("where_used")
+        .join("scripts")
+        .join(fm_file_name);
+    initialize_out_dir(&out_dir_path);
+
+    for (id, name, content) in index_script_calls(scripts, script_calls) {
+        let element_path = script_id_path_map
+            .get(&id)
+            .map(|path| {
+                path.iter()
+                    .map(|e| escape_filename(e))
+                    .collect::<Vec<_>>()
+                    .join("/")
+            })
+            .unwrap_or_default();
+
+        let output_dir = out_dir_path.join(element_path);
+        create_dir(&output_dir);
+        write_text_file(
+            &output_dir.join(format!(
+                "{}.txt",
+                escape_filename(&join_scope_id_and_name(&id, &name))
+            )),
+            &content,
+        );
+    }
+}
+
+/// Renders the callers and callees of every script. Calls of scripts in other files are skipped,
+/// they are recognized by id and name not matching a script of this file.
+fn index_script_calls(
+    scripts: &[(String, String)],
+    script_calls: &[ScriptCall],
+) -> Vec<(String, String, String)> {
+    let script_names: HashMap<&str, &str> = scripts
+        .iter()
+        .map(|(id, name)| (id.as_str(), name.as_str()))
+        .collect();
+
+    let mut called_by: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+    let mut calls: BTreeMap<&str, Vec<String>> = BTreeMap::new();
+    for script_call in script_calls {
+        if script_names.get(script_call.script_id.as_str())
+            != Some(&script_call.script_name.as_str())
+        {
+            continue;
+        }
+
+        let callers = called_by.entry(&script_call.script_id).or_default();
+        if !callers.contains(&script_call.location.as_str()) {
+            callers.push(&script_call.location);
+        }
+
+        if script_call.caller_type == "Script" {
+            let mut callee =
+                join_scope_id_and_name(&script_call.script_id, &script_call.script_name);
+            if let Some(step) = script_call.step {
+                callee.push_str(&format!(" ; Step: {}", step));
+            }
+            calls
+                .entry(&script_call.caller_id)
+                .or_default()
+                .push(callee);
+        }
+    }
+
+    scripts
+        .iter()
+        .map(|(id, name)| {
+            let mut content = vec!["Called by:".to_string()];
+            for caller in called_by.get(id.as_str()).into_iter().flatten() {
+                content.push(format!("\t{}", caller));
+            }
+            content.push("Calls:".to_string());
+            for callee in calls.get(id.as_str()).into_iter().flatten() {
+                content.push(format!("\t{}", callee));
+            }
+
+            (id.to_string(), name.to_string(), content.join("\n"))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_script_references() {
+        let xml_input = "
+        <Layout id=\"1\" name=\"Home\">
+            <ScriptTriggers membercount=\"1\">
+                <ScriptTrigger id=\"3\" action=\"OnObjectModify\" browseMode=\"True\">
+                    <ScriptReference id=\"83\" name=\"Modify\"></ScriptReference>
+                </ScriptTrigger>
+            </ScriptTriggers>
+            <action>
+                <Step enable=\"True\" id=\"1\" name=\"Perform Script\">
+                    <ScriptReference id=\"84\" name=\"Click\"></ScriptReference>
+                </Step>
+            </action>
+            <ScriptReference id=\"0\" name=\"\"></ScriptReference>
+            <action/>
+            <ScriptTrigger id=\"4\" action=\"OnObjectEnter\"/>
+            <ScriptReference id=\"85\" name=\"Menu\"/>
+        </Layout>
+        ";
+
+        assert_eq!(
+            find_script_references(xml_input),
+            vec![
+                (
+                    "83".to_string(),
+                    "Modify".to_string(),
+                    Some("Trigger: OnObjectModify".to_string())
+                ),
+                (
+                    "84".to_string(),
+                    "Click".to_string(),
+                    Some("Button".to_string())
+                ),
+                ("85".to_string(), "Menu".to_string(), None),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_index_script_calls() {
+        let scripts = vec![
+            ("1".to_string(), "Start".to_string()),
+            ("2".to_string(), "Sub".to_string()),
+        ];
+        let script_call =
+            |script_id: &str, script_name: &str, caller_type: &str, location: &str| ScriptCall {
+                script_id: script_id.to_string(),
+                script_name: script_name.to_string(),
+                caller_type: caller_type.to_string(),
+                caller_id: "1".to_string(),
+                step: Some(4),
+                step_name: "Perform Script".to_string(),
+                location: location.to_string(),
+            };
+
+        assert_eq!(
+            index_script_calls(
+                &scripts,
+                &[
+                    script_call("2", "Sub", "Script", "Script: Start - ID 1 ; Step: 4"),
+                    script_call("2", "Sub", "Layout", "Layout: Home - ID 1 ; Button"),
+                    script_call("2", "Other file", "Script", "Script: Start - ID 1 ; Step: 5"),
+                ]
+            ),
+            vec![
+                (
+                    "1".to_string(),
+                    "Start".to_string(),
+                    "Called by:\nCalls:\n\tSub - ID 2 ; Step: 4".to_string()
+                ),
+                (
+                    "2".to_string(),
+                    "Sub".to_string(),
+                    "Called by:\n\tScript: Start - ID 1 ; Step: 4\n\tLayout: Home - ID 1 ; Button\nCalls:"
+                        .to_string()
+                ),
+            ]
+        );
+    }
+}